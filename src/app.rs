@@ -1,25 +1,138 @@
-use crate::models::RegionOption;
-use crate::services::digitalocean::DigitalOceanClient;
-use anyhow::Result;
+use crate::models::{
+    generate_server_name, DeployedServer, Distro, ImageOption, ProviderKind, RegionOption,
+    ServerRequest, SizeOption, VpnConnection, VpnKind,
+};
+use crate::services::cloud_init::{CloudConfigExtra, CLOUD_INIT_VERSION};
+use crate::services::control::{ControlServer, ControlServerSettings};
+use crate::services::create_provider;
+use crate::services::openvpn::OpenVpnPki;
+use crate::services::provider::CloudProvider;
+use crate::services::shadowsocks::ShadowsocksSetup;
+use crate::services::state::{DeploymentRecord, StateStore};
+use crate::services::vpn::{Readiness, TailscaleBackend, VpnBackend};
+use crate::services::wireguard::WireGuardSetup;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
+/// Number of progress steps reported by `App::deploy_server_task`.
+pub const DEPLOY_STEPS: usize = 6;
+
+/// How long a deployment waits for the server to come online in the tailnet.
+pub const DEFAULT_TAILSCALE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Tags applied to generated auth keys unless others are configured.
+pub const DEFAULT_TAILSCALE_TAGS: &[&str] = &["tag:exit-node"];
+
+/// Client configs generated for WireGuard and OpenVPN servers unless told
+/// otherwise.
+pub const DEFAULT_CLIENTS: usize = 1;
+
 #[derive(Debug, Clone)]
 pub enum DeploymentMessage {
-    Progress { step: usize, status: String },
-    Complete { server_info: ServerInfo },
-    Error { message: String },
+    Progress {
+        step: usize,
+        status: String,
+    },
+    Complete {
+        server_info: Box<ServerInfo>,
+    },
+    Error {
+        message: String,
+        /// Server that was created before the failure and is still running.
+        created_server: Option<CreatedServer>,
+    },
+}
+
+/// What to deploy; the provider is passed separately.
+#[derive(Debug, Clone)]
+pub struct DeploymentConfig {
+    pub vpn: VpnKind,
+    /// `None` generates a single-use key through the control server API.
+    pub auth_key: Option<String>,
+    /// ACL tags for generated auth keys.
+    pub tailscale_tags: Vec<String>,
+    pub region: Option<RegionOption>,
+    pub size: Option<SizeOption>,
+    pub image: Option<ImageOption>,
+    /// Delete the server automatically if a later step fails.
+    pub rollback_on_failure: bool,
+    /// Coordination server to join, and API access to confirm the server
+    /// joined it.
+    pub control: ControlServerSettings,
+    pub tailscale_timeout: Duration,
+    /// Number of client configs to generate for WireGuard and OpenVPN.
+    pub client_count: usize,
+    /// Entries merged into the generated cloud-config.
+    pub cloud_init_extra: Option<CloudConfigExtra>,
+}
+
+/// A server created by a deployment that did not finish.
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedServer {
+    pub id: String,
+    pub name: String,
+}
+
+/// What the user chose to do from the welcome screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    Deploy,
+    ManageServers,
 }
 
 #[derive(Debug, Clone)]
 pub enum AppState {
     Welcome,
-    Auth { token: String, cursor: usize },
-    RegionSelect { selected_index: usize },
-    TailscaleAuth { auth_key: String, cursor: usize },
-    Loading { message: String },
-    Deploy { progress: DeployProgress },
-    Complete { server_info: ServerInfo },
-    Error { message: String },
+    ProviderSelect {
+        selected_index: usize,
+    },
+    Auth {
+        token: String,
+        cursor: usize,
+    },
+    SizeSelect {
+        selected_index: usize,
+    },
+    RegionSelect {
+        selected_index: usize,
+    },
+    ImageSelect {
+        selected_index: usize,
+    },
+    VpnSelect {
+        selected_index: usize,
+    },
+    TailscaleAuth {
+        auth_key: String,
+        cursor: usize,
+    },
+    Loading {
+        message: String,
+    },
+    Deploy {
+        progress: DeployProgress,
+    },
+    Complete {
+        server_info: Box<ServerInfo>,
+    },
+    /// A client config from `server_info` shown as a QR code.
+    ClientQr {
+        server_info: Box<ServerInfo>,
+        client_index: usize,
+    },
+    Servers {
+        selected_index: usize,
+    },
+    ConfirmDestroy {
+        selected_index: usize,
+    },
+    Error {
+        message: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -29,19 +142,108 @@ pub struct DeployProgress {
     pub status: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ServerInfo {
+    pub id: String,
     pub name: String,
+    /// Region slug, filled in from the server when none was chosen.
+    pub region: String,
+    /// Size slug; `None` when the provider default was used.
+    pub size: Option<String>,
+    #[serde(rename = "ipv4")]
     pub ip: String,
+    pub ipv6: Option<String>,
     pub cost: String,
+    pub monthly_cost: Option<f64>,
+    pub currency: Option<&'static str>,
+    #[serde(flatten)]
+    pub connection: VpnConnection,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A deployed server with its cost worked out from the size catalogue.
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerListing {
+    #[serde(flatten)]
+    pub server: DeployedServer,
+    pub monthly_cost: Option<f64>,
+    pub accumulated_cost: Option<f64>,
+    pub currency: Option<&'static str>,
+}
+
+impl ServerListing {
+    /// Cost so far and the monthly rate, e.g. `$1.23 ($4.00/month)`.
+    pub fn format_cost(&self) -> String {
+        match (self.accumulated_cost, self.monthly_cost, self.currency) {
+            (Some(accumulated), Some(monthly), Some(currency)) => format!(
+                "{}{:.2} ({}{:.2}/month)",
+                currency, accumulated, currency, monthly
+            ),
+            _ => "unknown".to_string(),
+        }
+    }
+}
+
+/// Result of tearing down a deployed server.
+#[derive(Debug, Clone, Serialize)]
+pub struct DestroyOutcome {
+    pub id: String,
+    pub name: String,
+    /// `None` when the state file does not record the server's VPN.
+    pub vpn: Option<VpnKind>,
+    /// Tailnet devices removed, or `None` when no control server API key was
+    /// available or the server was not in a tailnet.
+    pub tailnet_devices_removed: Option<usize>,
+}
+
+impl DestroyOutcome {
+    pub fn summary(&self) -> String {
+        if self.vpn.is_some_and(|vpn| vpn != VpnKind::Tailscale) {
+            return format!("Destroyed {}", self.name);
+        }
+
+        match self.tailnet_devices_removed {
+            Some(0) => format!(
+                "Destroyed {}; no matching tailnet device was found",
+                self.name
+            ),
+            Some(count) => format!(
+                "Destroyed {} and removed {} device(s) from your tailnet",
+                self.name, count
+            ),
+            None => format!(
+                "Destroyed {}. Set TS_API_KEY (or HEADSCALE_API_KEY) to also remove it from your tailnet",
+                self.name
+            ),
+        }
+    }
 }
 
 pub struct App {
     pub state: AppState,
     pub should_quit: bool,
-    do_client: Option<DigitalOceanClient>,
+    pub flow: Flow,
+    pub provider_kind: ProviderKind,
+    provider: Option<Arc<dyn CloudProvider>>,
     tailscale_auth_key: Option<String>,
+    pub regions: Vec<RegionOption>,
     pub selected_region: Option<RegionOption>,
+    sizes: Vec<SizeOption>,
+    pub selected_size: Option<SizeOption>,
+    pub images: Vec<ImageOption>,
+    pub selected_image: Option<ImageOption>,
+    pub servers: Vec<ServerListing>,
+    /// Outcome of the last action on the servers screen.
+    pub notice: Option<String>,
+    pub rollback_on_failure: bool,
+    pub vpn_kind: VpnKind,
+    pub tailscale_timeout: Duration,
+    pub tailscale_tags: Vec<String>,
+    pub control: ControlServerSettings,
+    pub client_count: usize,
+    pub cloud_init_extra: Option<CloudConfigExtra>,
+    /// Server left running by the last failed deployment.
+    pub orphaned_server: Option<CreatedServer>,
     deployment_receiver: Option<mpsc::UnboundedReceiver<DeploymentMessage>>,
 }
 
@@ -50,9 +252,29 @@ impl App {
         Self {
             state: AppState::Welcome,
             should_quit: false,
-            do_client: None,
+            flow: Flow::Deploy,
+            provider_kind: ProviderKind::DigitalOcean,
+            provider: None,
             tailscale_auth_key: None,
+            regions: Vec::new(),
             selected_region: None,
+            sizes: Vec::new(),
+            selected_size: None,
+            images: Vec::new(),
+            selected_image: None,
+            servers: Vec::new(),
+            notice: None,
+            rollback_on_failure: false,
+            vpn_kind: VpnKind::default(),
+            tailscale_timeout: DEFAULT_TAILSCALE_TIMEOUT,
+            tailscale_tags: DEFAULT_TAILSCALE_TAGS
+                .iter()
+                .map(|tag| tag.to_string())
+                .collect(),
+            control: ControlServerSettings::from_environment(None),
+            client_count: DEFAULT_CLIENTS,
+            cloud_init_extra: None,
+            orphaned_server: None,
             deployment_receiver: None,
         }
     }
@@ -60,41 +282,184 @@ impl App {
     pub async fn handle_enter(&mut self) -> Result<()> {
         match &self.state {
             AppState::Welcome => {
-                self.state = AppState::Auth {
-                    token: String::new(),
-                    cursor: 0,
+                self.flow = Flow::Deploy;
+                self.state = AppState::ProviderSelect { selected_index: 0 };
+            }
+            AppState::ProviderSelect { selected_index } => {
+                if let Some(kind) = ProviderKind::all().get(*selected_index) {
+                    self.provider_kind = *kind;
+                    self.state = AppState::Auth {
+                        token: String::new(),
+                        cursor: 0,
+                    };
+                }
+            }
+            AppState::Auth { token, .. }
+                if !token.is_empty() || !self.provider_kind.requires_token() =>
+            {
+                // Store provider client and move to region selection
+                let provider = match create_provider(self.provider_kind, token.clone()) {
+                    Ok(provider) => provider,
+                    Err(e) => {
+                        self.state = AppState::Error {
+                            message: e.to_string(),
+                        };
+                        return Ok(());
+                    }
+                };
+
+                if self.flow == Flow::ManageServers {
+                    self.state = match Self::load_servers(provider.as_ref()).await {
+                        Ok((servers, warning)) => {
+                            self.servers = servers;
+                            self.notice = warning;
+                            self.provider = Some(provider);
+                            AppState::Servers { selected_index: 0 }
+                        }
+                        Err(e) => AppState::Error {
+                            message: e.to_string(),
+                        },
+                    };
+                    return Ok(());
+                }
+
+                self.state = match Self::load_catalog(provider.as_ref()).await {
+                    Ok((regions, sizes, images)) => {
+                        self.regions = regions;
+                        self.sizes = sizes;
+                        self.images = images;
+                        self.selected_size = None;
+                        self.selected_image = None;
+
+                        let default_size = provider.default_size();
+                        self.provider = Some(provider);
+                        let selected_index = self
+                            .available_sizes()
+                            .iter()
+                            .position(|size| size.slug == default_size)
+                            .unwrap_or(0);
+
+                        AppState::SizeSelect { selected_index }
+                    }
+                    Err(e) => AppState::Error {
+                        message: e.to_string(),
+                    },
                 };
             }
-            AppState::Auth { token, .. } => {
-                if !token.is_empty() {
-                    // Store DO client and move to region selection
-                    self.do_client = Some(DigitalOceanClient::new(token.clone()));
+            AppState::SizeSelect { selected_index } => {
+                if let Some(size) = self.available_sizes().get(*selected_index) {
+                    self.selected_size = Some(size.clone());
                     self.state = AppState::RegionSelect { selected_index: 0 };
                 }
             }
             AppState::RegionSelect { selected_index } => {
-                let regions = RegionOption::available_regions();
-                if let Some(region) = regions.get(*selected_index) {
+                if let Some(region) = self.available_regions().get(*selected_index) {
                     self.selected_region = Some(region.clone());
-                    self.state = AppState::TailscaleAuth {
-                        auth_key: String::new(),
-                        cursor: 0,
+                    self.state = if self.images.is_empty() {
+                        // Nothing to choose from; deploy the provider's default image
+                        self.vpn_select()
+                    } else {
+                        let default_image = self
+                            .provider
+                            .as_ref()
+                            .map(|provider| provider.default_image())
+                            .unwrap_or_default();
+                        let selected_index = self
+                            .images
+                            .iter()
+                            .position(|image| image.slug == default_image)
+                            .unwrap_or(0);
+
+                        AppState::ImageSelect { selected_index }
                     };
                 }
             }
-            AppState::TailscaleAuth { auth_key, .. } => {
-                if !auth_key.is_empty() {
-                    self.tailscale_auth_key = Some(auth_key.clone());
-                    self.state = AppState::Loading {
-                        message: "Deploying your VPN server...".to_string(),
-                    };
-                    self.start_deployment().await?;
+            AppState::ImageSelect { selected_index } => {
+                if let Some(image) = self.images.get(*selected_index) {
+                    self.selected_image = Some(image.clone());
+                    self.state = self.vpn_select();
+                }
+            }
+            AppState::VpnSelect { selected_index } => {
+                if let Some(kind) = VpnKind::all().get(*selected_index) {
+                    self.vpn_kind = *kind;
+                    if self.vpn_kind == VpnKind::Tailscale {
+                        self.state = AppState::TailscaleAuth {
+                            auth_key: String::new(),
+                            cursor: 0,
+                        };
+                    } else {
+                        self.tailscale_auth_key = None;
+                        self.state = AppState::Loading {
+                            message: "Deploying your VPN server...".to_string(),
+                        };
+                        self.start_deployment().await?;
+                    }
                 }
             }
+            AppState::TailscaleAuth { auth_key, .. }
+                if !auth_key.is_empty() || self.control.can_generate_auth_keys() =>
+            {
+                self.tailscale_auth_key = Some(auth_key.clone()).filter(|key| !key.is_empty());
+                self.state = AppState::Loading {
+                    message: "Deploying your VPN server...".to_string(),
+                };
+                self.start_deployment().await?;
+            }
             AppState::Complete { .. } => {
                 self.should_quit = true;
             }
+            AppState::ClientQr { server_info, .. } => {
+                self.state = AppState::Complete {
+                    server_info: server_info.clone(),
+                };
+            }
+            AppState::Servers { .. } => {
+                self.state = AppState::Welcome;
+            }
+            AppState::ConfirmDestroy { selected_index } => {
+                let selected = self.servers.get(*selected_index).map(|l| l.server.clone());
+                if let (Some(provider), Some(server)) = (self.provider.clone(), selected) {
+                    let result = match self.control.connect().await {
+                        Ok(control) => {
+                            Self::destroy_server(
+                                provider.as_ref(),
+                                &server.id,
+                                &server.name,
+                                control.as_deref(),
+                            )
+                            .await
+                        }
+                        Err(e) => Err(e),
+                    };
+
+                    self.state = match result {
+                        Ok(outcome) => match Self::load_servers(provider.as_ref()).await {
+                            Ok((servers, warning)) => {
+                                self.servers = servers;
+                                self.notice = Some(match warning {
+                                    Some(warning) => format!("{}. {}", outcome.summary(), warning),
+                                    None => outcome.summary(),
+                                });
+                                AppState::Servers { selected_index: 0 }
+                            }
+                            Err(e) => AppState::Error {
+                                message: format!(
+                                    "{}, but refreshing the list failed: {}",
+                                    outcome.summary(),
+                                    e
+                                ),
+                            },
+                        },
+                        Err(e) => AppState::Error {
+                            message: e.to_string(),
+                        },
+                    };
+                }
+            }
             AppState::Error { .. } => {
+                // A leftover server still shows up under "My servers"
+                self.orphaned_server = None;
                 self.state = AppState::Welcome;
             }
             _ => {}
@@ -112,26 +477,51 @@ impl App {
 
     pub fn handle_char(&mut self, c: char) {
         match &mut self.state {
+            AppState::Welcome if matches!(c, 'm' | 'M') => {
+                self.flow = Flow::ManageServers;
+                self.state = AppState::ProviderSelect { selected_index: 0 };
+            }
+            AppState::Servers { selected_index }
+                if matches!(c, 'd' | 'D') && *selected_index < self.servers.len() =>
+            {
+                self.state = AppState::ConfirmDestroy {
+                    selected_index: *selected_index,
+                };
+            }
+            AppState::Complete { server_info }
+                if matches!(c, 'r' | 'R') && !server_info.connection.qr_clients().is_empty() =>
+            {
+                self.state = AppState::ClientQr {
+                    server_info: server_info.clone(),
+                    client_index: 0,
+                };
+            }
+            AppState::ConfirmDestroy { selected_index } if matches!(c, 'n' | 'N') => {
+                self.state = AppState::Servers {
+                    selected_index: *selected_index,
+                };
+            }
+            AppState::Error { message }
+                if matches!(c, 'c' | 'C') && self.orphaned_server.is_some() =>
+            {
+                let message = message.clone();
+                self.start_cleanup(message);
+            }
             AppState::Auth { token, cursor } => {
                 token.insert(*cursor, c);
                 *cursor += 1;
             }
-            AppState::RegionSelect { selected_index } => {
-                let regions = RegionOption::available_regions();
-                match c {
-                    'j' | 's' => {
-                        if *selected_index < regions.len() - 1 {
-                            *selected_index += 1;
-                        }
-                    }
-                    'k' | 'w' => {
-                        if *selected_index > 0 {
-                            *selected_index -= 1;
-                        }
-                    }
-                    _ => {}
-                }
-            }
+            AppState::ProviderSelect { .. }
+            | AppState::SizeSelect { .. }
+            | AppState::RegionSelect { .. }
+            | AppState::ImageSelect { .. }
+            | AppState::VpnSelect { .. }
+            | AppState::ClientQr { .. }
+            | AppState::Servers { .. } => match c {
+                'j' | 's' => self.handle_down(),
+                'k' | 'w' => self.handle_up(),
+                _ => {}
+            },
             AppState::TailscaleAuth { auth_key, cursor } => {
                 auth_key.insert(*cursor, c);
                 *cursor += 1;
@@ -142,42 +532,55 @@ impl App {
 
     pub fn handle_backspace(&mut self) {
         match &mut self.state {
-            AppState::Auth { token, cursor } => {
-                if *cursor > 0 {
-                    token.remove(*cursor - 1);
-                    *cursor -= 1;
-                }
+            AppState::Auth { token, cursor } if *cursor > 0 => {
+                token.remove(*cursor - 1);
+                *cursor -= 1;
             }
-            AppState::TailscaleAuth { auth_key, cursor } => {
-                if *cursor > 0 {
-                    auth_key.remove(*cursor - 1);
-                    *cursor -= 1;
-                }
+            AppState::TailscaleAuth { auth_key, cursor } if *cursor > 0 => {
+                auth_key.remove(*cursor - 1);
+                *cursor -= 1;
             }
             _ => {}
         }
     }
 
     pub fn handle_up(&mut self) {
-        match &mut self.state {
-            AppState::RegionSelect { selected_index } => {
-                if *selected_index > 0 {
-                    *selected_index -= 1;
-                }
+        if let Some((selected_index, _)) = self.list_selection() {
+            if *selected_index > 0 {
+                *selected_index -= 1;
             }
-            _ => {}
         }
     }
 
     pub fn handle_down(&mut self) {
+        if let Some((selected_index, len)) = self.list_selection() {
+            if *selected_index + 1 < len {
+                *selected_index += 1;
+            }
+        }
+    }
+
+    /// The cursor and item count of the list shown on the current screen, if any.
+    fn list_selection(&mut self) -> Option<(&mut usize, usize)> {
+        let size_count = self.available_sizes().len();
+        let region_count = self.available_regions().len();
+        let image_count = self.images.len();
+        let server_count = self.servers.len();
+
         match &mut self.state {
-            AppState::RegionSelect { selected_index } => {
-                let regions = RegionOption::available_regions();
-                if *selected_index < regions.len() - 1 {
-                    *selected_index += 1;
-                }
+            AppState::ProviderSelect { selected_index } => {
+                Some((selected_index, ProviderKind::all().len()))
             }
-            _ => {}
+            AppState::SizeSelect { selected_index } => Some((selected_index, size_count)),
+            AppState::RegionSelect { selected_index } => Some((selected_index, region_count)),
+            AppState::ImageSelect { selected_index } => Some((selected_index, image_count)),
+            AppState::VpnSelect { selected_index } => Some((selected_index, VpnKind::all().len())),
+            AppState::ClientQr {
+                server_info,
+                client_index,
+            } => Some((client_index, server_info.connection.qr_clients().len())),
+            AppState::Servers { selected_index } => Some((selected_index, server_count)),
+            _ => None,
         }
     }
 
@@ -190,7 +593,7 @@ impl App {
                         self.state = AppState::Deploy {
                             progress: DeployProgress {
                                 current_step: step,
-                                total_steps: 6,
+                                total_steps: DEPLOY_STEPS,
                                 status,
                             },
                         };
@@ -199,7 +602,11 @@ impl App {
                         self.state = AppState::Complete { server_info };
                         self.deployment_receiver = None;
                     }
-                    DeploymentMessage::Error { message } => {
+                    DeploymentMessage::Error {
+                        message,
+                        created_server,
+                    } => {
+                        self.orphaned_server = created_server;
                         self.state = AppState::Error { message };
                         self.deployment_receiver = None;
                     }
@@ -209,29 +616,284 @@ impl App {
         Ok(())
     }
 
+    /// The VPN selection screen, starting on the last choice.
+    fn vpn_select(&self) -> AppState {
+        AppState::VpnSelect {
+            selected_index: VpnKind::all()
+                .iter()
+                .position(|kind| *kind == self.vpn_kind)
+                .unwrap_or(0),
+        }
+    }
+
+    /// Sizes offered in at least one region, cheapest first.
+    pub fn available_sizes(&self) -> Vec<SizeOption> {
+        let mut sizes: Vec<SizeOption> = self
+            .sizes
+            .iter()
+            .filter(|size| {
+                self.regions
+                    .iter()
+                    .any(|region| size.is_available_in(&region.slug))
+            })
+            .cloned()
+            .collect();
+        sizes.sort_by(|a, b| a.price_monthly.total_cmp(&b.price_monthly));
+        sizes
+    }
+
+    /// Regions offering the selected size, or every region if no size is known.
+    pub fn available_regions(&self) -> Vec<RegionOption> {
+        self.regions
+            .iter()
+            .filter(|region| {
+                self.selected_size
+                    .as_ref()
+                    .is_none_or(|size| size.is_available_in(&region.slug))
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub async fn load_catalog(
+        provider: &dyn CloudProvider,
+    ) -> Result<(Vec<RegionOption>, Vec<SizeOption>, Vec<ImageOption>)> {
+        provider
+            .validate_credentials()
+            .await
+            .map_err(|e| anyhow!("Invalid credentials: {}", e))?;
+
+        let regions = provider
+            .list_regions()
+            .await
+            .map_err(|e| anyhow!("Failed to load regions: {}", e))?;
+
+        if regions.is_empty() {
+            return Err(anyhow!("{} has no regions available", provider.name()));
+        }
+
+        let sizes = provider
+            .list_sizes()
+            .await
+            .map_err(|e| anyhow!("Failed to load server sizes: {}", e))?;
+
+        let mut images = provider
+            .list_images()
+            .await
+            .map_err(|e| anyhow!("Failed to load images: {}", e))?;
+        images.sort_by(|a, b| a.name.cmp(&b.name));
+        images.dedup_by(|a, b| a.name == b.name);
+
+        Ok((regions, sizes, images))
+    }
+
+    /// Servers created by this tool, oldest first, priced from the size
+    /// catalogue, and a warning if some of them could not be listed.
+    pub async fn load_servers(
+        provider: &dyn CloudProvider,
+    ) -> Result<(Vec<ServerListing>, Option<String>)> {
+        provider
+            .validate_credentials()
+            .await
+            .map_err(|e| anyhow!("Invalid credentials: {}", e))?;
+
+        let mut list = provider
+            .list_servers()
+            .await
+            .map_err(|e| anyhow!("Failed to list servers: {}", e))?;
+        list.servers.sort_by_key(|server| server.created_at);
+
+        // The local state is only a cache of the provider, so failing to
+        // update it never fails the command
+        let _ = StateStore::update(|store| store.reconcile(provider.kind(), &list));
+
+        // Pricing is best effort; the list is still useful without it
+        let sizes = provider.list_sizes().await.unwrap_or_default();
+
+        let warning = list.warning();
+        let servers = list
+            .servers
+            .into_iter()
+            .map(|server| {
+                let size = sizes.iter().find(|size| size.slug == server.size);
+                ServerListing {
+                    monthly_cost: size.map(|size| size.price_in(&server.region)),
+                    accumulated_cost: size
+                        .map(|size| size.accumulated_cost(&server.region, server.hours_running())),
+                    currency: size.map(|size| size.currency),
+                    server,
+                }
+            })
+            .collect();
+        Ok((servers, warning))
+    }
+
+    /// Deletes the server and, given a coordination server client, its
+    /// tailnet node.
+    pub async fn destroy_server(
+        provider: &dyn CloudProvider,
+        id: &str,
+        name: &str,
+        control: Option<&dyn ControlServer>,
+    ) -> Result<DestroyOutcome> {
+        provider
+            .destroy_server(id)
+            .await
+            .map_err(|e| anyhow!("Failed to destroy {}: {}", name, e))?;
+        // Servers whose VPN is not recorded may be in a tailnet
+        let vpn = StateStore::open()
+            .ok()
+            .and_then(|store| store.vpn(provider.kind(), id));
+        let _ = StateStore::update(|store| store.remove(provider.kind(), id));
+
+        // The server name doubles as its tailnet hostname
+        let in_tailnet = vpn.is_none_or(|vpn| vpn == VpnKind::Tailscale);
+        let tailnet_devices_removed = match control.filter(|_| in_tailnet) {
+            Some(control) => Some(control.delete_nodes_by_hostname(name).await.map_err(|e| {
+                anyhow!(
+                    "Destroyed {}, but removing it from the tailnet failed: {}",
+                    name,
+                    e
+                )
+            })?),
+            None => None,
+        };
+
+        Ok(DestroyOutcome {
+            id: id.to_string(),
+            name: name.to_string(),
+            vpn,
+            tailnet_devices_removed,
+        })
+    }
+
     async fn start_deployment(&mut self) -> Result<()> {
         let (tx, rx) = mpsc::unbounded_channel();
         self.deployment_receiver = Some(rx);
 
-        let client = self.do_client.clone();
-        let auth_key = self.tailscale_auth_key.clone();
-        let region = self.selected_region.clone();
+        let provider = self.provider.clone();
+        let config = DeploymentConfig {
+            vpn: self.vpn_kind,
+            auth_key: self.tailscale_auth_key.clone(),
+            tailscale_tags: self.tailscale_tags.clone(),
+            region: self.selected_region.clone(),
+            size: self.selected_size.clone(),
+            image: self.selected_image.clone(),
+            rollback_on_failure: self.rollback_on_failure,
+            control: self.control.clone(),
+            tailscale_timeout: self.tailscale_timeout,
+            client_count: self.client_count,
+            cloud_init_extra: self.cloud_init_extra.clone(),
+        };
 
         tokio::spawn(async move {
-            if let (Some(client), Some(auth_key)) = (client, auth_key) {
-                let _ = Self::deploy_server_task(client, auth_key, region, tx).await;
+            if let Some(provider) = provider {
+                let _ = Self::deploy_server_task(provider, config, tx).await;
             }
         });
 
         Ok(())
     }
 
-    async fn deploy_server_task(
-        client: DigitalOceanClient,
-        auth_key: String,
-        region: Option<RegionOption>,
+    /// Deletes the server left behind by a failed deployment in the background.
+    fn start_cleanup(&mut self, message: String) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.deployment_receiver = Some(rx);
+
+        let provider = self.provider.clone();
+        let server = self.orphaned_server.take();
+        self.state = AppState::Loading {
+            message: "Deleting the unfinished server...".to_string(),
+        };
+
+        tokio::spawn(async move {
+            if let (Some(provider), Some(server)) = (provider, server) {
+                let (message, created_server) =
+                    match Self::rollback(provider.as_ref(), &server).await {
+                        Ok(()) => (
+                            format!(
+                                "{} The unfinished server {} was deleted.",
+                                message, server.name
+                            ),
+                            None,
+                        ),
+                        Err(e) => (
+                            format!("{} Deleting {} failed: {}", message, server.name, e),
+                            Some(server),
+                        ),
+                    };
+                let _ = tx.send(DeploymentMessage::Error {
+                    message,
+                    created_server,
+                });
+            }
+        });
+    }
+
+    /// Deletes a server created by a deployment that did not finish.
+    pub async fn rollback(provider: &dyn CloudProvider, server: &CreatedServer) -> Result<()> {
+        provider.destroy_server(&server.id).await?;
+        let _ = StateStore::update(|store| store.remove(provider.kind(), &server.id));
+        Ok(())
+    }
+
+    /// Runs a deployment, reporting progress and the outcome through `tx`.
+    ///
+    /// If a step fails after the server was created, the error names the
+    /// server so it can be cleaned up, or deletes it right away when
+    /// `config.rollback_on_failure` is set.
+    pub async fn deploy_server_task(
+        provider: Arc<dyn CloudProvider>,
+        config: DeploymentConfig,
         tx: mpsc::UnboundedSender<DeploymentMessage>,
     ) -> Result<()> {
+        let mut created_server = None;
+        let result =
+            Self::run_deployment(provider.as_ref(), &config, &mut created_server, &tx).await;
+
+        match result {
+            Ok(server_info) => {
+                let _ = tx.send(DeploymentMessage::Complete {
+                    server_info: Box::new(server_info),
+                });
+            }
+            Err(e) => {
+                let mut message = e.to_string();
+                let created_server = match created_server {
+                    Some(server) if config.rollback_on_failure => {
+                        match Self::rollback(provider.as_ref(), &server).await {
+                            Ok(()) => {
+                                message.push_str(" The unfinished server was deleted.");
+                                None
+                            }
+                            Err(e) => {
+                                message.push_str(&format!(
+                                    " Deleting the unfinished server failed: {}",
+                                    e
+                                ));
+                                Some(server)
+                            }
+                        }
+                    }
+                    other => other,
+                };
+
+                let _ = tx.send(DeploymentMessage::Error {
+                    message,
+                    created_server,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn run_deployment(
+        provider: &dyn CloudProvider,
+        config: &DeploymentConfig,
+        created_server: &mut Option<CreatedServer>,
+        tx: &mpsc::UnboundedSender<DeploymentMessage>,
+    ) -> Result<ServerInfo> {
         let send_progress = |step: usize, status: String| {
             let _ = tx.send(DeploymentMessage::Progress { step, status });
         };
@@ -239,91 +901,179 @@ impl App {
         // Step 1: Validate credentials
         send_progress(1, "Validating credentials...".to_string());
 
-        match client.validate_token().await {
-            Ok(_) => {
-                send_progress(2, "Creating server...".to_string());
+        provider
+            .validate_credentials()
+            .await
+            .map_err(|e| anyhow!("Invalid credentials: {}", e))?;
 
-                // Step 2: Create droplet
-                match client.create_droplet(&auth_key, region).await {
-                    Ok(droplet) => {
-                        send_progress(3, "Waiting for server to be ready...".to_string());
+        // The server name doubles as its tailnet hostname
+        let name = generate_server_name();
+        let mut backend = Self::prepare_backend(config, &name).await?;
 
-                        // Step 3: Wait for server
-                        let _server_info = client.wait_for_droplet_ready(droplet.id).await?;
+        send_progress(2, format!("Creating server on {}...", provider.name()));
 
-                        send_progress(4, "Installing and configuring Tailscale...".to_string());
+        // Step 2: Create server
+        // Provider default images are Ubuntu
+        let distro = config
+            .image
+            .as_ref()
+            .map(|image| image.distro)
+            .unwrap_or(Distro::Ubuntu);
+        let mut cloud_config = backend.cloud_config(&name, distro);
+        if let Some(extra) = &config.cloud_init_extra {
+            cloud_config.merge(extra)?;
+        }
+        let user_data = provider.user_data(&cloud_config)?;
+        let mut request = ServerRequest::new(name, user_data);
+        request.region = config.region.as_ref().map(|region| region.slug.clone());
+        request.size = config.size.as_ref().map(|size| size.slug.clone());
+        request.image = config.image.as_ref().map(|image| image.slug.clone());
 
-                        // Step 4: Wait for Tailscale setup to complete
-                        // The cloud-init script will handle the entire setup process
-                        Self::wait_for_tailscale_setup(tx.clone()).await?;
+        let server_id = provider
+            .create_server(&request)
+            .await
+            .map_err(|e| anyhow!("Failed to create server: {}", e))?;
+        let created_at = Utc::now();
+        *created_server = Some(CreatedServer {
+            id: server_id.clone(),
+            name: request.name.clone(),
+        });
 
-                        send_progress(5, "Finalizing server setup...".to_string());
+        let size = config.size.as_ref();
+        let monthly_cost = size.map(|size| match &config.region {
+            Some(region) => size.price_in(&region.slug),
+            None => size.price_monthly,
+        });
 
-                        // Step 5: Get final server info
-                        let server_info = client.wait_for_droplet_ready(droplet.id).await?;
+        // Recorded as soon as the server exists so a failed deployment is
+        // tracked too; the provider listing still finds it if this fails
+        let mut record = DeploymentRecord {
+            provider: provider.kind(),
+            id: server_id.clone(),
+            name: request.name.clone(),
+            region: request.region.clone(),
+            size: request.size.clone(),
+            vpn: Some(backend.kind()),
+            tailnet_hostname: (backend.kind() == VpnKind::Tailscale).then(|| request.name.clone()),
+            created_at,
+            cloud_init_version: Some(CLOUD_INIT_VERSION),
+            cloud_init_extra: config.cloud_init_extra.is_some(),
+            monthly_cost,
+            currency: size.map(|size| size.currency.to_string()),
+        };
+        let _ = StateStore::update(|store| store.insert(record.clone()));
 
-                        // Complete setup
-                        let _ = tx.send(DeploymentMessage::Complete {
-                            server_info: ServerInfo {
-                                name: server_info.name,
-                                ip: server_info.ip,
-                                cost: "$4/month".to_string(),
-                            },
-                        });
-                    }
-                    Err(e) => {
-                        let _ = tx.send(DeploymentMessage::Error {
-                            message: format!("Failed to create server: {}", e),
-                        });
-                    }
-                }
+        send_progress(3, "Waiting for server to be ready...".to_string());
+
+        // Step 3: Wait for server
+        let ready_server = provider
+            .wait_for_server_ready(&server_id)
+            .await
+            .map_err(|e| anyhow!("Server did not become ready: {}", e))?;
+
+        // Without a region the provider picked one; record where it went
+        if record.region.is_none() {
+            record.region = Some(ready_server.region.clone());
+            record.monthly_cost = size.map(|size| size.price_in(&ready_server.region));
+            let _ = StateStore::update(|store| store.insert(record.clone()));
+        }
+
+        let vpn_name = backend.kind().display_name();
+        send_progress(4, format!("Installing and configuring {}...", vpn_name));
+
+        // Step 4: Open the VPN's ports and wait for the setup script
+        let ports = backend.inbound_ports();
+        if !ports.is_empty() {
+            provider
+                .open_ports(&server_id, &ports)
+                .await
+                .map_err(|e| anyhow!("Failed to open the {} ports: {}", vpn_name, e))?;
+        }
+        let step_progress = |step: usize| {
+            move |status: String| {
+                let _ = tx.send(DeploymentMessage::Progress { step, status });
             }
-            Err(e) => {
-                let _ = tx.send(DeploymentMessage::Error {
-                    message: format!("Invalid credentials: {}", e),
-                });
+        };
+        match backend.readiness() {
+            Readiness::Polled { timeout } => {
+                send_progress(
+                    4,
+                    format!(
+                        "Waiting up to {}s for {} to come up...",
+                        timeout.as_secs(),
+                        vpn_name
+                    ),
+                );
+                backend
+                    .poll_until_ready(&request.name, &step_progress(4))
+                    .await?
+            }
+            Readiness::FixedWait(wait) => {
+                send_progress(
+                    4,
+                    format!(
+                        "Waiting for {} setup (not verified from outside)...",
+                        vpn_name
+                    ),
+                );
+                tokio::time::sleep(wait).await;
             }
         }
 
-        Ok(())
-    }
+        send_progress(5, "Finalizing server setup...".to_string());
 
-    async fn wait_for_tailscale_setup(tx: mpsc::UnboundedSender<DeploymentMessage>) -> Result<()> {
-        let send_progress = |step: usize, status: String| {
-            let _ = tx.send(DeploymentMessage::Progress { step, status });
-        };
+        // Step 5: Work out how devices connect, now the endpoint is known
+        let connection = backend
+            .connection(&request.name, &ready_server.ip, &step_progress(5))
+            .await?;
 
-        // Show detailed setup progress
-        let setup_steps = vec![
-            "Downloading and installing Tailscale...",
-            "Configuring IP forwarding...",
-            "Starting Tailscale daemon...",
-            "Connecting to your Tailnet...",
-            "Enabling SSH access...",
-            "Configuring as exit node...",
-            "Finalizing setup...",
-        ];
-
-        for (i, step_msg) in setup_steps.iter().enumerate() {
-            send_progress(4, step_msg.to_string());
-
-            // Wait time varies based on step complexity
-            let wait_time = match i {
-                0 => 15, // Installing takes longest
-                1 => 5,  // IP forwarding config
-                2 => 10, // Starting daemon
-                3 => 15, // Connecting to Tailnet
-                4 => 5,  // SSH setup
-                5 => 5,  // Exit node config
-                _ => 10, // Final steps
-            };
-
-            tokio::time::sleep(tokio::time::Duration::from_secs(wait_time)).await;
-        }
+        // Get final server info
+        let server_info = provider
+            .wait_for_server_ready(&server_id)
+            .await
+            .map_err(|e| anyhow!("Server did not become ready: {}", e))?;
 
-        send_progress(4, "Tailscale setup completed successfully!".to_string());
-        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+        Ok(ServerInfo {
+            id: server_id,
+            name: server_info.name,
+            region: ready_server.region,
+            size: request.size.clone(),
+            ip: server_info.ip,
+            ipv6: server_info.ipv6,
+            cost: match (size, record.monthly_cost) {
+                (Some(size), Some(price)) => size.format_price(price),
+                _ => "See your provider's pricing".to_string(),
+            },
+            monthly_cost: record.monthly_cost,
+            currency: size.map(|size| size.currency),
+            connection,
+            created_at,
+        })
+    }
 
-        Ok(())
+    /// Sets up the chosen VPN for a server named `server_name`: connects to
+    /// the control server for Tailscale, or generates keys locally.
+    async fn prepare_backend(
+        config: &DeploymentConfig,
+        server_name: &str,
+    ) -> Result<Box<dyn VpnBackend>> {
+        Ok(match config.vpn {
+            VpnKind::Tailscale => Box::new(
+                TailscaleBackend::connect(
+                    &config.control,
+                    config.auth_key.clone(),
+                    &config.tailscale_tags,
+                    config.tailscale_timeout,
+                    server_name,
+                )
+                .await?,
+            ),
+            VpnKind::WireGuard => Box::new(WireGuardSetup::generate(config.client_count)?),
+            VpnKind::OpenVpn => Box::new(
+                OpenVpnPki::generate(config.client_count)
+                    .map_err(|e| anyhow!("Failed to generate certificates: {}", e))?,
+            ),
+            VpnKind::Shadowsocks => Box::new(ShadowsocksSetup::generate(config.client_count)?),
+        })
     }
 }
@@ -0,0 +1,577 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::app::{DEFAULT_CLIENTS, DEFAULT_TAILSCALE_TAGS, DEFAULT_TAILSCALE_TIMEOUT};
+use crate::models::{ProviderKind, VpnKind};
+use crate::services::cloud_init::CloudConfigExtra;
+use crate::services::control::ControlServerSettings;
+use crate::services::headscale::HeadscaleCredentials;
+use crate::services::openvpn::MAX_OPENVPN_CLIENTS;
+use crate::services::shadowsocks::MAX_SHADOWSOCKS_CLIENTS;
+use crate::services::tailscale::TailscaleCredentials;
+use crate::services::wireguard::MAX_WIREGUARD_CLIENTS;
+
+/// What the binary was asked to do on the command line.
+#[derive(Debug)]
+pub enum Command {
+    Interactive(InteractiveArgs),
+    Help,
+    Version,
+    Deploy(DeployArgs),
+    List(ListArgs),
+    Destroy(DestroyArgs),
+    Status(StatusArgs),
+}
+
+/// How headless commands report their results on stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Options for the interactive setup.
+#[derive(Debug)]
+pub struct InteractiveArgs {
+    /// Preselected on the VPN selection screen.
+    pub vpn: VpnKind,
+    pub control: ControlServerSettings,
+    pub rollback_on_failure: bool,
+    pub tailscale_timeout: Duration,
+    pub tailscale_tags: Vec<String>,
+    pub client_count: usize,
+    pub cloud_init_extra: Option<CloudConfigExtra>,
+}
+
+/// Provider selection shared by every headless command.
+#[derive(Debug)]
+pub struct ProviderArgs {
+    pub kind: ProviderKind,
+    /// API token, or for Lightsail an optional AWS profile name.
+    pub credential: String,
+}
+
+/// Options for `vpn-deployer deploy`, resolved from flags and environment.
+#[derive(Debug)]
+pub struct DeployArgs {
+    pub provider: ProviderArgs,
+    pub vpn: VpnKind,
+    /// `None` when an auth key is to be generated through the control server API.
+    pub auth_key: Option<String>,
+    pub region: Option<String>,
+    pub size: Option<String>,
+    pub image: Option<String>,
+    pub rollback_on_failure: bool,
+    pub control: ControlServerSettings,
+    pub tailscale_timeout: Duration,
+    pub tailscale_tags: Vec<String>,
+    pub client_count: usize,
+    pub cloud_init_extra: Option<CloudConfigExtra>,
+    pub output: OutputFormat,
+}
+
+/// Options for `vpn-deployer list`.
+#[derive(Debug)]
+pub struct ListArgs {
+    pub provider: ProviderArgs,
+    pub output: OutputFormat,
+}
+
+/// Options for `vpn-deployer destroy`.
+#[derive(Debug)]
+pub struct DestroyArgs {
+    pub provider: ProviderArgs,
+    /// Server name or provider id.
+    pub target: String,
+    pub control: ControlServerSettings,
+    pub output: OutputFormat,
+}
+
+/// Options for `vpn-deployer status`.
+#[derive(Debug)]
+pub struct StatusArgs {
+    pub output: OutputFormat,
+}
+
+const PROVIDER_FLAGS: &[&str] = &["--provider", "--token", "--aws-profile", "--output"];
+
+/// Coordination server selection and API credentials.
+const CONTROL_FLAGS: &[&str] = &[
+    "--login-server",
+    "--tailscale-api-key",
+    "--headscale-api-key",
+    "--headscale-user",
+];
+
+/// VPN selection and how the server is set up.
+const VPN_FLAGS: &[&str] = &["--vpn", "--clients", "--cloud-init-extra"];
+
+/// Flags that take no value.
+const SWITCHES: &[&str] = &["--rollback-on-failure"];
+
+/// Parses the arguments following the program name.
+pub fn parse(args: &[String]) -> Result<Command> {
+    let Some(first) = args.first() else {
+        return parse_interactive(args).map(Command::Interactive);
+    };
+
+    match first.as_str() {
+        "--help" | "-h" => Ok(Command::Help),
+        "--version" | "-v" => Ok(Command::Version),
+        flag if flag.starts_with("--") => parse_interactive(args).map(Command::Interactive),
+        "deploy" => parse_deploy(&args[1..]).map(Command::Deploy),
+        "list" => parse_list(&args[1..]).map(Command::List),
+        "destroy" => parse_destroy(&args[1..]).map(Command::Destroy),
+        "status" => parse_status(&args[1..]).map(Command::Status),
+        other => Err(anyhow!("Unknown argument: {}", other)),
+    }
+}
+
+fn parse_interactive(args: &[String]) -> Result<InteractiveArgs> {
+    let mut flags = parse_flags(
+        "vpn-deployer",
+        args,
+        &[
+            VPN_FLAGS,
+            CONTROL_FLAGS,
+            &[
+                "--rollback-on-failure",
+                "--tailscale-timeout",
+                "--tailscale-tags",
+            ],
+        ]
+        .concat(),
+    )?;
+
+    let vpn = vpn_kind(&mut flags)?;
+    Ok(InteractiveArgs {
+        vpn,
+        control: control_settings(&mut flags)?,
+        rollback_on_failure: flags.remove("--rollback-on-failure").is_some(),
+        tailscale_timeout: tailscale_timeout(&mut flags)?,
+        tailscale_tags: tailscale_tags(&mut flags),
+        client_count: client_count(&mut flags, vpn)?,
+        cloud_init_extra: cloud_init_extra(&mut flags)?,
+    })
+}
+
+fn parse_deploy(args: &[String]) -> Result<DeployArgs> {
+    let mut flags = parse_flags(
+        "deploy",
+        args,
+        &[
+            PROVIDER_FLAGS,
+            VPN_FLAGS,
+            CONTROL_FLAGS,
+            &[
+                "--auth-key",
+                "--region",
+                "--size",
+                "--image",
+                "--rollback-on-failure",
+                "--tailscale-timeout",
+                "--tailscale-tags",
+            ],
+        ]
+        .concat(),
+    )?;
+
+    let vpn = vpn_kind(&mut flags)?;
+    let control = control_settings(&mut flags)?;
+    let auth_key = flags
+        .remove("--auth-key")
+        .or_else(|| env_var_value("TS_AUTHKEY"));
+    if vpn == VpnKind::Tailscale && auth_key.is_none() && !control.can_generate_auth_keys() {
+        return Err(match control.login_server {
+            Some(_) => anyhow!(
+                "Missing auth key: pass --auth-key, set TS_AUTHKEY, or set \
+                 HEADSCALE_API_KEY and HEADSCALE_USER to generate one"
+            ),
+            None => anyhow!(
+                "Missing Tailscale auth key: pass --auth-key, set TS_AUTHKEY, or set \
+                 TS_OAUTH_CLIENT_ID and TS_OAUTH_CLIENT_SECRET to generate one"
+            ),
+        });
+    }
+
+    Ok(DeployArgs {
+        provider: provider_args(&mut flags)?,
+        vpn,
+        auth_key,
+        region: flags.remove("--region"),
+        size: flags.remove("--size"),
+        image: flags.remove("--image"),
+        rollback_on_failure: flags.remove("--rollback-on-failure").is_some(),
+        control,
+        tailscale_timeout: tailscale_timeout(&mut flags)?,
+        tailscale_tags: tailscale_tags(&mut flags),
+        client_count: client_count(&mut flags, vpn)?,
+        cloud_init_extra: cloud_init_extra(&mut flags)?,
+        output: output_format(&mut flags)?,
+    })
+}
+
+fn parse_list(args: &[String]) -> Result<ListArgs> {
+    let mut flags = parse_flags("list", args, PROVIDER_FLAGS)?;
+
+    Ok(ListArgs {
+        provider: provider_args(&mut flags)?,
+        output: output_format(&mut flags)?,
+    })
+}
+
+fn parse_destroy(args: &[String]) -> Result<DestroyArgs> {
+    let (target, rest) = match args.split_first() {
+        Some((target, rest)) if !target.starts_with("--") => (target.clone(), rest),
+        _ => return Err(anyhow!("Missing server to destroy: destroy <name|id>")),
+    };
+    let mut flags = parse_flags("destroy", rest, &[PROVIDER_FLAGS, CONTROL_FLAGS].concat())?;
+
+    Ok(DestroyArgs {
+        provider: provider_args(&mut flags)?,
+        target,
+        control: control_settings(&mut flags)?,
+        output: output_format(&mut flags)?,
+    })
+}
+
+fn parse_status(args: &[String]) -> Result<StatusArgs> {
+    let mut flags = parse_flags("status", args, &["--output"])?;
+
+    Ok(StatusArgs {
+        output: output_format(&mut flags)?,
+    })
+}
+
+/// Collects `--flag value` and `--flag=value` pairs, rejecting unknown flags.
+/// Switches are recorded with an empty value.
+fn parse_flags(
+    command: &str,
+    args: &[String],
+    allowed: &[&str],
+) -> Result<HashMap<String, String>> {
+    let mut flags = HashMap::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+
+        if !allowed.contains(&flag) {
+            return Err(anyhow!("Unknown option for {}: {}", command, flag));
+        }
+
+        if SWITCHES.contains(&flag) {
+            flags.insert(flag.to_string(), String::new());
+            continue;
+        }
+
+        let value = value
+            .or_else(|| args.next().cloned())
+            .ok_or_else(|| anyhow!("Missing value for {}", flag))?;
+        flags.insert(flag.to_string(), value);
+    }
+
+    Ok(flags)
+}
+
+fn provider_args(flags: &mut HashMap<String, String>) -> Result<ProviderArgs> {
+    let kind = match flags.remove("--provider") {
+        Some(slug) => {
+            ProviderKind::from_slug(&slug).ok_or_else(|| anyhow!("Unknown provider: {}", slug))?
+        }
+        None => ProviderKind::DigitalOcean,
+    };
+
+    let credential = match kind.token_env_var() {
+        Some(env_var) => flags
+            .remove("--token")
+            .or_else(|| env_var_value(env_var))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Missing {} API token: pass --token or set {}",
+                    kind.display_name(),
+                    env_var
+                )
+            })?,
+        // Lightsail reads the standard AWS credentials itself
+        None => flags.remove("--aws-profile").unwrap_or_default(),
+    };
+
+    Ok(ProviderArgs { kind, credential })
+}
+
+fn output_format(flags: &mut HashMap<String, String>) -> Result<OutputFormat> {
+    match flags.remove("--output").as_deref() {
+        None | Some("text") => Ok(OutputFormat::Text),
+        Some("json") => Ok(OutputFormat::Json),
+        Some(other) => Err(anyhow!(
+            "Unknown output format: {} (expected text or json)",
+            other
+        )),
+    }
+}
+
+fn vpn_kind(flags: &mut HashMap<String, String>) -> Result<VpnKind> {
+    match flags.remove("--vpn") {
+        Some(slug) => VpnKind::from_slug(&slug).ok_or_else(|| anyhow!("Unknown VPN: {}", slug)),
+        None => Ok(VpnKind::default()),
+    }
+}
+
+fn client_count(flags: &mut HashMap<String, String>, vpn: VpnKind) -> Result<usize> {
+    let max = match vpn {
+        VpnKind::OpenVpn => MAX_OPENVPN_CLIENTS,
+        VpnKind::Shadowsocks => MAX_SHADOWSOCKS_CLIENTS,
+        VpnKind::Tailscale | VpnKind::WireGuard => MAX_WIREGUARD_CLIENTS,
+    };
+    match flags.remove("--clients") {
+        Some(count) => count
+            .parse()
+            .ok()
+            .filter(|count| (1..=max).contains(count))
+            .ok_or_else(|| anyhow!("Invalid --clients: {} (expected 1 to {})", count, max)),
+        None => Ok(DEFAULT_CLIENTS),
+    }
+}
+
+/// Read up front so a bad file fails before anything is created.
+fn cloud_init_extra(flags: &mut HashMap<String, String>) -> Result<Option<CloudConfigExtra>> {
+    flags
+        .remove("--cloud-init-extra")
+        .map(|path| CloudConfigExtra::load(Path::new(&path)))
+        .transpose()
+}
+
+/// Control server flags, falling back to credentials in the environment.
+fn control_settings(flags: &mut HashMap<String, String>) -> Result<ControlServerSettings> {
+    let login_server = flags.remove("--login-server");
+    if let Some(url) = &login_server {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(anyhow!("Invalid --login-server: {} (expected a URL)", url));
+        }
+    }
+
+    let mut settings = ControlServerSettings::from_environment(login_server);
+    if let Some(api_key) = flags.remove("--tailscale-api-key") {
+        settings.tailscale = Some(TailscaleCredentials::ApiKey(api_key));
+    }
+    if let Some(api_key) = flags.remove("--headscale-api-key") {
+        settings.headscale = Some(HeadscaleCredentials {
+            api_key,
+            user: env_var_value("HEADSCALE_USER"),
+        });
+    }
+    if let Some(user) = flags.remove("--headscale-user") {
+        let headscale = settings.headscale.as_mut().ok_or_else(|| {
+            anyhow!("--headscale-user requires --headscale-api-key or HEADSCALE_API_KEY")
+        })?;
+        headscale.user = Some(user);
+    }
+
+    Ok(settings)
+}
+
+fn tailscale_timeout(flags: &mut HashMap<String, String>) -> Result<Duration> {
+    match flags.remove("--tailscale-timeout") {
+        Some(seconds) => seconds.parse().map(Duration::from_secs).map_err(|_| {
+            anyhow!(
+                "Invalid --tailscale-timeout: {} (expected seconds)",
+                seconds
+            )
+        }),
+        None => Ok(DEFAULT_TAILSCALE_TIMEOUT),
+    }
+}
+
+/// Comma-separated `--tailscale-tags`, or the default tags.
+fn tailscale_tags(flags: &mut HashMap<String, String>) -> Vec<String> {
+    match flags.remove("--tailscale-tags") {
+        Some(tags) => tags
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        None => DEFAULT_TAILSCALE_TAGS
+            .iter()
+            .map(|tag| tag.to_string())
+            .collect(),
+    }
+}
+
+fn env_var_value(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn interactive(arguments: &[&str]) -> Result<InteractiveArgs> {
+        parse_interactive(&args(arguments))
+    }
+
+    #[test]
+    fn flags_take_separate_or_inline_values() {
+        let flags = parse_flags(
+            "deploy",
+            &args(&["--region", "ams3", "--size=s-1vcpu-1gb", "--image=a=b"]),
+            &["--region", "--size", "--image"],
+        )
+        .unwrap();
+
+        assert_eq!(flags["--region"], "ams3");
+        assert_eq!(flags["--size"], "s-1vcpu-1gb");
+        assert_eq!(flags["--image"], "a=b");
+    }
+
+    #[test]
+    fn switches_take_no_value() {
+        let flags = parse_flags(
+            "deploy",
+            &args(&["--rollback-on-failure", "--region", "ams3"]),
+            &["--rollback-on-failure", "--region"],
+        )
+        .unwrap();
+
+        assert_eq!(flags["--rollback-on-failure"], "");
+        assert_eq!(flags["--region"], "ams3");
+    }
+
+    #[test]
+    fn unknown_flags_and_missing_values_are_errors() {
+        let unknown = parse_flags("list", &args(&["--region", "ams3"]), PROVIDER_FLAGS);
+        assert_eq!(
+            unknown.unwrap_err().to_string(),
+            "Unknown option for list: --region"
+        );
+
+        let missing = parse_flags("list", &args(&["--provider"]), PROVIDER_FLAGS);
+        assert_eq!(
+            missing.unwrap_err().to_string(),
+            "Missing value for --provider"
+        );
+
+        assert!(parse(&args(&["deploy", "ams3"])).is_err());
+    }
+
+    #[test]
+    fn provider_token_falls_back_to_the_environment() {
+        // No other test reads LINODE_TOKEN
+        std::env::set_var("LINODE_TOKEN", "from-env");
+
+        let Command::List(list) = parse(&args(&["list", "--provider", "linode"])).unwrap() else {
+            panic!("expected list");
+        };
+        assert_eq!(list.provider.kind, ProviderKind::Linode);
+        assert_eq!(list.provider.credential, "from-env");
+
+        let Command::List(list) = parse(&args(&[
+            "list",
+            "--provider=linode",
+            "--token",
+            "from-flag",
+        ]))
+        .unwrap() else {
+            panic!("expected list");
+        };
+        assert_eq!(list.provider.credential, "from-flag");
+
+        std::env::remove_var("LINODE_TOKEN");
+        assert!(parse(&args(&["list", "--provider", "linode"])).is_err());
+    }
+
+    #[test]
+    fn client_count_is_bounded_per_vpn() {
+        assert_eq!(interactive(&[]).unwrap().client_count, DEFAULT_CLIENTS);
+        assert_eq!(
+            interactive(&["--vpn", "wireguard", "--clients", "253"])
+                .unwrap()
+                .client_count,
+            253
+        );
+        assert_eq!(
+            interactive(&["--vpn", "shadowsocks", "--clients=16"])
+                .unwrap()
+                .client_count,
+            16
+        );
+
+        for count in ["0", "17", "-1", "two"] {
+            let error = interactive(&["--vpn", "shadowsocks", "--clients", count]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("Invalid --clients: {} (expected 1 to 16)", count)
+            );
+        }
+        assert!(interactive(&["--vpn", "openvpn", "--clients", "254"]).is_err());
+    }
+
+    #[test]
+    fn tailscale_timeout_is_whole_seconds() {
+        assert_eq!(
+            interactive(&[]).unwrap().tailscale_timeout,
+            DEFAULT_TAILSCALE_TIMEOUT
+        );
+        assert_eq!(
+            interactive(&["--tailscale-timeout", "90"])
+                .unwrap()
+                .tailscale_timeout,
+            Duration::from_secs(90)
+        );
+
+        for seconds in ["10m", "-5", "1.5", ""] {
+            let error = interactive(&["--tailscale-timeout", seconds]).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!(
+                    "Invalid --tailscale-timeout: {} (expected seconds)",
+                    seconds
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn headscale_user_needs_an_api_key() {
+        // No other test sets HEADSCALE_API_KEY
+        std::env::remove_var("HEADSCALE_API_KEY");
+        assert_eq!(
+            interactive(&["--headscale-user", "vpn"])
+                .unwrap_err()
+                .to_string(),
+            "--headscale-user requires --headscale-api-key or HEADSCALE_API_KEY"
+        );
+
+        let settings = interactive(&["--headscale-api-key", "key", "--headscale-user", "vpn"])
+            .unwrap()
+            .control;
+        assert_eq!(settings.headscale.unwrap().user.as_deref(), Some("vpn"));
+    }
+
+    #[test]
+    fn destroy_takes_the_target_first() {
+        let Command::Destroy(destroy) =
+            parse(&args(&["destroy", "tailscale-vpn-1", "--token", "t"])).unwrap()
+        else {
+            panic!("expected destroy");
+        };
+        assert_eq!(destroy.target, "tailscale-vpn-1");
+        assert_eq!(destroy.provider.credential, "t");
+
+        for arguments in [&["destroy"][..], &["destroy", "--token", "t"]] {
+            assert_eq!(
+                parse(&args(arguments)).unwrap_err().to_string(),
+                "Missing server to destroy: destroy <name|id>"
+            );
+        }
+    }
+}
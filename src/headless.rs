@@ -0,0 +1,342 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use crate::app::{
+    App, DeploymentConfig, DeploymentMessage, DestroyOutcome, ServerInfo, ServerListing,
+    DEPLOY_STEPS,
+};
+use crate::cli::{DeployArgs, DestroyArgs, ListArgs, OutputFormat, StatusArgs};
+use crate::models::VpnConnection;
+use crate::services::create_provider;
+use crate::services::state::{DeploymentRecord, StateStore};
+
+/// Runs a deployment without the TUI. Progress goes to stderr so stdout only
+/// carries the resulting server details.
+pub async fn deploy(args: DeployArgs) -> Result<()> {
+    let output = args.output;
+    let result = run_deploy(args).await;
+
+    match (&result, output) {
+        (Ok(server_info), OutputFormat::Json) => {
+            println!("{}", serde_json::to_string_pretty(server_info)?)
+        }
+        (Ok(server_info), OutputFormat::Text) => print_server_info(server_info),
+        (Err(e), OutputFormat::Json) => {
+            println!("{}", json!({ "error": e.to_string() }))
+        }
+        (Err(_), OutputFormat::Text) => {}
+    }
+
+    result.map(|_| ())
+}
+
+async fn run_deploy(args: DeployArgs) -> Result<ServerInfo> {
+    let provider = create_provider(args.provider.kind, args.provider.credential)?;
+
+    eprintln!("Loading {} regions and sizes...", provider.name());
+    let (regions, sizes, images) = App::load_catalog(provider.as_ref()).await?;
+
+    let size_slug = args.size.unwrap_or_else(|| provider.default_size());
+    let size = sizes
+        .into_iter()
+        .find(|size| size.slug == size_slug)
+        .ok_or_else(|| anyhow!("Unknown size: {}", size_slug))?;
+
+    let region = match args.region {
+        Some(slug) => {
+            let region = regions
+                .into_iter()
+                .find(|region| region.slug == slug)
+                .ok_or_else(|| anyhow!("Unknown region: {}", slug))?;
+            if !size.is_available_in(&region.slug) {
+                return Err(anyhow!(
+                    "Size {} is not available in {}",
+                    size.slug,
+                    region.slug
+                ));
+            }
+            Some(region)
+        }
+        None => None,
+    };
+
+    let image = match args.image {
+        Some(slug) => Some(
+            images
+                .into_iter()
+                .find(|image| image.slug == slug)
+                .ok_or_else(|| anyhow!("Unknown or unsupported image: {}", slug))?,
+        ),
+        None => None,
+    };
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let config = DeploymentConfig {
+        vpn: args.vpn,
+        auth_key: args.auth_key,
+        tailscale_tags: args.tailscale_tags,
+        region,
+        size: Some(size),
+        image,
+        rollback_on_failure: args.rollback_on_failure,
+        control: args.control,
+        tailscale_timeout: args.tailscale_timeout,
+        client_count: args.client_count,
+        cloud_init_extra: args.cloud_init_extra,
+    };
+    let task = tokio::spawn(App::deploy_server_task(provider, config, tx));
+
+    while let Some(message) = rx.recv().await {
+        match message {
+            DeploymentMessage::Progress { step, status } => {
+                eprintln!("[{}/{}] {}", step, DEPLOY_STEPS, status);
+            }
+            DeploymentMessage::Complete { server_info } => return Ok(*server_info),
+            DeploymentMessage::Error {
+                message,
+                created_server: Some(server),
+            } => {
+                return Err(anyhow!(
+                    "{} Server {} is still running; delete it with `vpn-deployer destroy {}`",
+                    message,
+                    server.name,
+                    server.id
+                ))
+            }
+            DeploymentMessage::Error { message, .. } => return Err(anyhow!(message)),
+        }
+    }
+
+    // The task stopped without reporting completion
+    task.await??;
+    Err(anyhow!("Deployment ended without completing"))
+}
+
+fn print_server_info(server_info: &ServerInfo) {
+    println!("id: {}", server_info.id);
+    println!("name: {}", server_info.name);
+    println!("ipv4: {}", server_info.ip);
+    if let Some(ipv6) = &server_info.ipv6 {
+        println!("ipv6: {}", ipv6);
+    }
+    match &server_info.connection {
+        VpnConnection::Tailscale {
+            tailnet_hostname,
+            exit_node,
+            login_server,
+        } => {
+            println!("tailnet hostname: {}", tailnet_hostname);
+            if let Some(login_server) = login_server {
+                println!("login server: {}", login_server);
+            }
+            println!("exit node: {}", exit_node.summary());
+        }
+        VpnConnection::WireGuard { clients } => {
+            for client in clients {
+                println!("client config: {}", client.path.display());
+            }
+        }
+        VpnConnection::OpenVpn { clients } => {
+            for client in clients {
+                println!("client profile: {}", client.path.display());
+            }
+        }
+        VpnConnection::Shadowsocks { clients } => {
+            for client in clients {
+                println!("access url: {}", client.config);
+                println!("saved to: {}", client.path.display());
+            }
+        }
+    }
+    println!("cost: {}", server_info.cost);
+    println!("created: {}", server_info.created_at.to_rfc3339());
+}
+
+/// Prints the servers this tool has deployed with the given provider.
+pub async fn list(args: ListArgs) -> Result<()> {
+    let result = async {
+        let provider = create_provider(args.provider.kind, args.provider.credential)?;
+        App::load_servers(provider.as_ref()).await
+    }
+    .await;
+
+    if let Ok((_, Some(warning))) = &result {
+        eprintln!("Warning: {}", warning);
+    }
+
+    match (&result, args.output) {
+        (Ok((servers, _)), OutputFormat::Json) => {
+            println!("{}", serde_json::to_string_pretty(servers)?)
+        }
+        (Ok((servers, _)), OutputFormat::Text) => print_server_list(servers),
+        (Err(e), OutputFormat::Json) => {
+            println!("{}", json!({ "error": e.to_string() }))
+        }
+        (Err(_), OutputFormat::Text) => {}
+    }
+
+    result.map(|_| ())
+}
+
+fn print_server_list(servers: &[ServerListing]) {
+    if servers.is_empty() {
+        eprintln!("No VPN servers found");
+        return;
+    }
+
+    println!(
+        "{:<26}{:<16}{:<10}{:<17}{:<20}{:<10}COST",
+        "NAME", "REGION", "STATUS", "IP", "SIZE", "AGE"
+    );
+    for listing in servers {
+        let server = &listing.server;
+        println!(
+            "{:<26}{:<16}{:<10}{:<17}{:<20}{:<10}{}",
+            server.name,
+            server.region,
+            server.status,
+            server.ip.as_deref().unwrap_or("-"),
+            server.size,
+            server.age(),
+            listing.format_cost()
+        );
+    }
+}
+
+/// Deletes a deployed server, matched by id or name, and its tailnet device
+/// when a Tailscale API key is available.
+pub async fn destroy(args: DestroyArgs) -> Result<()> {
+    let output = args.output;
+    let result = run_destroy(args).await;
+
+    match (&result, output) {
+        (Ok(outcome), OutputFormat::Json) => {
+            println!("{}", serde_json::to_string_pretty(outcome)?)
+        }
+        (Ok(outcome), OutputFormat::Text) => println!("{}", outcome.summary()),
+        (Err(e), OutputFormat::Json) => {
+            println!("{}", json!({ "error": e.to_string() }))
+        }
+        (Err(_), OutputFormat::Text) => {}
+    }
+
+    result.map(|_| ())
+}
+
+async fn run_destroy(args: DestroyArgs) -> Result<DestroyOutcome> {
+    let provider = create_provider(args.provider.kind, args.provider.credential)?;
+
+    // The provider is asked even for recorded servers, so a record left by a
+    // server deleted elsewhere is pruned rather than its stale id deleted
+    eprintln!("Looking up {} on {}...", args.target, provider.name());
+    let list = provider.list_servers().await?;
+    let recorded = StateStore::open()
+        .map(|store| !store.find(provider.kind(), &args.target).is_empty())
+        .unwrap_or(false);
+    let _ = StateStore::update(|store| store.reconcile(provider.kind(), &list));
+
+    let mut matches: Vec<(String, String)> = list
+        .servers
+        .iter()
+        .filter(|server| server.id == args.target || server.name == args.target)
+        .map(|server| (server.id.clone(), server.name.clone()))
+        .collect();
+    if matches.is_empty() {
+        // Recorded servers in regions that could not be listed are still
+        // deleted by their recorded id
+        matches = StateStore::open()
+            .map(|store| {
+                store
+                    .find(provider.kind(), &args.target)
+                    .into_iter()
+                    .filter(|record| {
+                        record
+                            .region
+                            .as_ref()
+                            .is_some_and(|region| list.failed_regions.contains(region))
+                    })
+                    .map(|record| (record.id.clone(), record.name.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    let (id, name) = match matches.as_slice() {
+        [server] => server,
+        [] if recorded && list.failed_regions.is_empty() => {
+            return Err(anyhow!(
+                "{} no longer exists on {}; removed its local record",
+                args.target,
+                provider.name()
+            ))
+        }
+        [] => return Err(anyhow!("No VPN server named or with id {}", args.target)),
+        _ => {
+            return Err(anyhow!(
+                "Several servers are named {}; pass the server id instead",
+                args.target
+            ))
+        }
+    };
+
+    eprintln!("Destroying {} ({})...", name, id);
+    let control = args.control.connect().await?;
+    App::destroy_server(provider.as_ref(), id, name, control.as_deref()).await
+}
+
+/// A recorded deployment with its cost so far.
+#[derive(Serialize)]
+struct StatusEntry<'a> {
+    #[serde(flatten)]
+    record: &'a DeploymentRecord,
+    accumulated_cost: Option<f64>,
+}
+
+/// Prints the deployments recorded on this machine, across all providers,
+/// without contacting any API.
+pub fn status(args: StatusArgs) -> Result<()> {
+    let store = StateStore::open()?;
+    let deployments = store.deployments();
+
+    match args.output {
+        OutputFormat::Json => {
+            let entries: Vec<_> = deployments
+                .iter()
+                .map(|record| StatusEntry {
+                    record,
+                    accumulated_cost: record.accumulated_cost(),
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        OutputFormat::Text => print_status(deployments),
+    }
+
+    Ok(())
+}
+
+fn print_status(deployments: &[DeploymentRecord]) {
+    if deployments.is_empty() {
+        eprintln!("No deployments recorded on this machine");
+        return;
+    }
+
+    println!(
+        "{:<26}{:<14}{:<11}{:<16}{:<20}{:<10}COST",
+        "NAME", "PROVIDER", "VPN", "REGION", "SIZE", "AGE"
+    );
+    for record in deployments {
+        println!(
+            "{:<26}{:<14}{:<11}{:<16}{:<20}{:<10}{}",
+            record.name,
+            record.provider.slug(),
+            record.vpn.map_or("unknown", |vpn| vpn.slug()),
+            record.region.as_deref().unwrap_or("default"),
+            record.size.as_deref().unwrap_or("default"),
+            record.age(),
+            record.format_cost()
+        );
+    }
+}
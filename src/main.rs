@@ -12,34 +12,106 @@ use std::io;
 use tokio::time::Duration;
 
 mod app;
+mod cli;
+mod headless;
 mod models;
 mod services;
 mod ui;
 
 use app::{App, AppState};
+use cli::Command;
 
 fn print_help() {
     println!("VPN Deployer v{}", env!("CARGO_PKG_VERSION"));
-    println!("Deploy your own VPN server on DigitalOcean with Tailscale");
+    println!("Deploy your own VPN server in the cloud with Tailscale, WireGuard, OpenVPN");
+    println!("or Shadowsocks");
     println!();
     println!("USAGE:");
     println!("    vpn-deployer [OPTIONS]");
+    println!("    vpn-deployer deploy [DEPLOY OPTIONS]");
+    println!("    vpn-deployer list [PROVIDER OPTIONS]");
+    println!("    vpn-deployer destroy <NAME|ID> [DESTROY OPTIONS]");
+    println!("    vpn-deployer status [--output <FORMAT>]");
     println!();
     println!("OPTIONS:");
-    println!("    -h, --help     Print this help message");
-    println!("    -v, --version  Print version information");
+    println!("    -h, --help               Print this help message");
+    println!("    -v, --version            Print version information");
+    println!();
+    println!("COMMANDS:");
+    println!("    deploy         Deploy a server without the interactive interface.");
+    println!("                   Progress is printed to stderr, server details to stdout.");
+    println!("    list           List the VPN servers this tool has deployed, with their");
+    println!("                   age and estimated cost so far.");
+    println!("    destroy        Delete a deployed server by name or id, and remove it from");
+    println!("                   your tailnet when a Tailscale API key is available.");
+    println!("    status         Show the deployments recorded on this machine and their");
+    println!("                   estimated cost, without contacting any provider.");
+    println!();
+    println!("PROVIDER OPTIONS:");
+    println!("    --provider <NAME>     digitalocean (default), hetzner, vultr, linode, lightsail");
+    println!("    --token <TOKEN>       Provider API token. Defaults to DIGITALOCEAN_TOKEN,");
+    println!("                          HCLOUD_TOKEN, VULTR_API_KEY or LINODE_TOKEN");
+    println!("    --aws-profile <NAME>  AWS profile for Lightsail (defaults to AWS_PROFILE)");
+    println!("    --output <FORMAT>     text (default) or json for machine-readable results");
+    println!();
+    println!("DEPLOY OPTIONS (in addition to the provider options):");
+    println!("    --auth-key <KEY>      Tailscale auth key. Defaults to TS_AUTHKEY; generated");
+    println!("                          when a Tailscale OAuth client or Headscale user is");
+    println!("                          configured");
+    println!("    --region <SLUG>       Region to deploy in (provider default if omitted)");
+    println!("    --size <SLUG>         Server size (cheapest default if omitted)");
+    println!("    --image <SLUG>        Operating system image (Ubuntu LTS if omitted)");
+    println!();
+    println!("SETUP OPTIONS (interactive and deploy):");
+    println!("    --rollback-on-failure Delete the server if a later deployment step fails");
+    println!("    --tailscale-timeout <SECS>");
+    println!("                          How long to wait for the server to join your tailnet");
+    println!("                          (default 600)");
+    println!("    --tailscale-tags <TAGS>");
+    println!("                          Comma-separated tags for generated auth keys");
+    println!("                          (default tag:exit-node)");
+    println!();
+    println!("VPN OPTIONS (interactive and deploy):");
+    println!("    --vpn <NAME>          tailscale (default), wireguard, openvpn or shadowsocks");
+    println!("    --clients <N>         Client configs or access URLs to generate for");
+    println!("                          WireGuard, OpenVPN and Shadowsocks (default 1)");
+    println!("    --cloud-init-extra <FILE>");
+    println!("                          YAML file with packages, write_files and runcmd entries");
+    println!("                          to add to the server's cloud-config");
+    println!();
+    println!("CONTROL SERVER OPTIONS (interactive, deploy and destroy):");
+    println!("    --login-server <URL>  Headscale server to join instead of tailscale.com");
+    println!("    --tailscale-api-key <KEY>");
+    println!("                          Tailscale API access token used to confirm the server");
+    println!("                          joined your tailnet. Defaults to TS_API_KEY");
+    println!("    --headscale-api-key <KEY>");
+    println!("                          Headscale API key. Defaults to HEADSCALE_API_KEY");
+    println!("    --headscale-user <NAME>");
+    println!("                          Headscale user that generated pre-auth keys belong to.");
+    println!("                          Defaults to HEADSCALE_USER");
+    println!();
+    println!("    Instead of an API access token, the Tailscale API can be reached with an");
+    println!("    OAuth client set in TS_OAUTH_CLIENT_ID and TS_OAUTH_CLIENT_SECRET.");
     println!();
     println!("DESCRIPTION:");
-    println!("    This tool helps you deploy a VPN server on DigitalOcean using Tailscale.");
-    println!("    You'll need:");
-    println!("    • A DigitalOcean account and API token");
-    println!("    • A Tailscale account and auth key");
+    println!("    This tool helps you deploy a Tailscale exit node or a WireGuard, OpenVPN");
+    println!("    or Shadowsocks server on DigitalOcean, Hetzner Cloud, Vultr, Linode or");
+    println!("    AWS Lightsail. You'll need:");
+    println!("    • An account and API token for one of those providers");
+    println!("      (Lightsail uses your standard AWS credentials)");
+    println!("    • For Tailscale only: a Tailscale account and auth key, or a Headscale");
+    println!("      server and pre-auth key");
     println!();
-    println!("    The tool will guide you through the setup process interactively.");
+    println!("    Without a command, the tool guides you through the setup interactively.");
     println!();
     println!("EXAMPLES:");
     println!("    vpn-deployer          Start the interactive setup");
     println!("    vpn-deployer --help   Show this help message");
+    println!("    DIGITALOCEAN_TOKEN=... TS_AUTHKEY=... vpn-deployer deploy --region ams3");
+    println!("    DIGITALOCEAN_TOKEN=... vpn-deployer deploy --vpn wireguard --clients 3");
+    println!("    HCLOUD_TOKEN=... vpn-deployer deploy --provider hetzner --vpn openvpn");
+    println!("    HCLOUD_TOKEN=... vpn-deployer list --provider hetzner");
+    println!("    vpn-deployer destroy tailscale-vpn-1a2b3c4d --token ...");
     println!();
     println!("For more information, visit: https://github.com/rymnc/vpn-deployer");
 }
@@ -47,25 +119,52 @@ fn print_help() {
 #[tokio::main]
 async fn main() -> Result<()> {
     // Handle command line arguments
-    let args: Vec<String> = std::env::args().collect();
+    let args: Vec<String> = std::env::args().skip(1).collect();
 
-    if args.len() > 1 {
-        match args[1].as_str() {
-            "--help" | "-h" => {
-                print_help();
-                return Ok(());
+    let interactive_args = match cli::parse(&args) {
+        Ok(Command::Interactive(interactive_args)) => interactive_args,
+        Ok(Command::Help) => {
+            print_help();
+            return Ok(());
+        }
+        Ok(Command::Version) => {
+            println!("vpn-deployer {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        Ok(Command::Deploy(deploy_args)) => {
+            if let Err(err) = headless::deploy(deploy_args).await {
+                eprintln!("Error: {}", err);
+                std::process::exit(1);
             }
-            "--version" | "-v" => {
-                println!("vpn-deployer {}", env!("CARGO_PKG_VERSION"));
-                return Ok(());
+            return Ok(());
+        }
+        Ok(Command::List(list_args)) => {
+            if let Err(err) = headless::list(list_args).await {
+                eprintln!("Error: {}", err);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Ok(Command::Status(status_args)) => {
+            if let Err(err) = headless::status(status_args) {
+                eprintln!("Error: {}", err);
+                std::process::exit(1);
             }
-            _ => {
-                eprintln!("Unknown argument: {}", args[1]);
-                eprintln!("Use --help for usage information");
+            return Ok(());
+        }
+        Ok(Command::Destroy(destroy_args)) => {
+            if let Err(err) = headless::destroy(destroy_args).await {
+                eprintln!("Error: {}", err);
                 std::process::exit(1);
             }
+            return Ok(());
         }
-    }
+        Err(err) => {
+            eprintln!("{}", err);
+            eprintln!("Use --help for usage information");
+            std::process::exit(1);
+        }
+    };
 
     // Setup terminal
     enable_raw_mode()?;
@@ -76,6 +175,13 @@ async fn main() -> Result<()> {
 
     // Create app
     let mut app = App::new();
+    app.rollback_on_failure = interactive_args.rollback_on_failure;
+    app.tailscale_timeout = interactive_args.tailscale_timeout;
+    app.tailscale_tags = interactive_args.tailscale_tags;
+    app.control = interactive_args.control;
+    app.vpn_kind = interactive_args.vpn;
+    app.client_count = interactive_args.client_count;
+    app.cloud_init_extra = interactive_args.cloud_init_extra;
     let res = run_app(&mut terminal, &mut app).await;
 
     // Restore terminal
@@ -104,7 +210,10 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Resul
                     KeyCode::Char('q') => {
                         if matches!(
                             app.state,
-                            AppState::Welcome | AppState::Complete { .. } | AppState::Error { .. }
+                            AppState::Welcome
+                                | AppState::Complete { .. }
+                                | AppState::Servers { .. }
+                                | AppState::Error { .. }
                         ) {
                             return Ok(());
                         }
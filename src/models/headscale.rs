@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Node {
+    pub id: String,
+    /// Hostname the node registered with.
+    pub name: String,
+    /// Name the node is known by in the tailnet.
+    #[serde(default)]
+    pub given_name: String,
+    #[serde(default)]
+    pub online: bool,
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Routes the node advertises.
+    #[serde(default)]
+    pub available_routes: Vec<String>,
+    #[serde(default)]
+    pub approved_routes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodesResponse {
+    pub nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeResponse {
+    pub node: Node,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsersResponse {
+    pub users: Vec<User>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePreAuthKeyRequest {
+    /// User id the key belongs to.
+    pub user: String,
+    pub reusable: bool,
+    pub ephemeral: bool,
+    pub expiration: DateTime<Utc>,
+    pub acl_tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PreAuthKey {
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreAuthKeyResponse {
+    pub pre_auth_key: PreAuthKey,
+}
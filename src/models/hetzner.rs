@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Server {
+    pub id: u64,
+    pub name: String,
+    pub status: String,
+    pub public_net: PublicNet,
+    pub server_type: ServerTypeRef,
+    pub datacenter: Datacenter,
+    pub created: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerTypeRef {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Datacenter {
+    pub location: LocationRef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationRef {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicNet {
+    pub ipv4: Option<Ipv4>,
+    pub ipv6: Option<Ipv6>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ipv4 {
+    pub ip: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ipv6 {
+    /// The /64 network assigned to the server, e.g. `2001:db8::/64`.
+    pub ip: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerResponse {
+    pub server: Server,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServersResponse {
+    pub servers: Vec<Server>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateServerRequest {
+    pub name: String,
+    pub server_type: String,
+    pub image: String,
+    pub location: String,
+    pub user_data: String,
+    pub labels: HashMap<String, String>,
+    pub start_after_create: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Location {
+    pub name: String,
+    pub description: String,
+    pub country: String,
+    pub city: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationsResponse {
+    pub locations: Vec<Location>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerType {
+    pub name: String,
+    pub cores: u32,
+    /// Memory in GB.
+    pub memory: f64,
+    /// Disk in GB.
+    pub disk: u32,
+    #[serde(default)]
+    pub deprecated: Option<bool>,
+    pub prices: Vec<ServerTypePrice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerTypePrice {
+    pub location: String,
+    pub price_monthly: Price,
+    /// Included outgoing traffic in bytes.
+    #[serde(default)]
+    pub included_traffic: u64,
+}
+
+/// Hetzner returns prices as decimal strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Price {
+    pub net: String,
+    pub gross: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerTypesResponse {
+    pub server_types: Vec<ServerType>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Image {
+    pub name: Option<String>,
+    pub os_flavor: String,
+    pub os_version: Option<String>,
+    pub deprecated: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagesResponse {
+    pub images: Vec<Image>,
+}
+
+impl Default for CreateServerRequest {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            server_type: "cx22".to_string(), // Cheapest shared vCPU option
+            image: "ubuntu-24.04".to_string(),
+            location: "nbg1".to_string(),
+            user_data: String::new(),
+            labels: HashMap::new(),
+            start_after_create: true,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Instance {
+    pub name: String,
+    pub state: InstanceState,
+    pub public_ip_address: Option<String>,
+    #[serde(default)]
+    pub ipv6_addresses: Vec<String>,
+    pub bundle_id: String,
+    pub location: ResourceLocation,
+    /// Seconds since the Unix epoch.
+    pub created_at: f64,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceLocation {
+    pub region_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceState {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetInstanceResponse {
+    pub instance: Instance,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetInstancesResponse {
+    pub instances: Vec<Instance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateInstancesRequest {
+    pub instance_names: Vec<String>,
+    pub availability_zone: String,
+    pub blueprint_id: String,
+    pub bundle_id: String,
+    /// Launch script. Lightsail prepends its own shell script to it, so it
+    /// has to be a shell script too.
+    pub user_data: String,
+    pub ip_address_type: String,
+    pub tags: Vec<Tag>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub name: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub availability_zones: Vec<AvailabilityZone>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailabilityZone {
+    pub zone_name: String,
+    pub state: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetRegionsResponse {
+    pub regions: Vec<Region>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bundle {
+    pub bundle_id: String,
+    pub price: f64,
+    pub ram_size_in_gb: f64,
+    pub cpu_count: u32,
+    pub disk_size_in_gb: u32,
+    pub transfer_per_month_in_gb: u32,
+    pub is_active: bool,
+    #[serde(default)]
+    pub supported_platforms: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBundlesResponse {
+    pub bundles: Vec<Bundle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Blueprint {
+    pub blueprint_id: String,
+    pub name: String,
+    pub version: String,
+    #[serde(rename = "type")]
+    pub blueprint_type: String,
+    pub platform: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBlueprintsResponse {
+    pub blueprints: Vec<Blueprint>,
+}
+
+impl Default for CreateInstancesRequest {
+    fn default() -> Self {
+        Self {
+            instance_names: vec![],
+            availability_zone: String::new(),
+            blueprint_id: "ubuntu_24_04".to_string(),
+            bundle_id: "nano_3_0".to_string(), // Cheapest dual-stack bundle
+            user_data: String::new(),
+            ip_address_type: "dualstack".to_string(),
+            tags: vec![],
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    pub id: u64,
+    pub label: String,
+    pub status: String,
+    pub ipv4: Vec<String>,
+    /// SLAAC address with prefix length, e.g. `2600:3c00::f03c:91ff:fe12:3456/128`.
+    pub ipv6: Option<String>,
+    pub region: String,
+    #[serde(rename = "type")]
+    pub instance_type: String,
+    /// UTC timestamp without an offset, e.g. `2018-01-01T00:01:01`.
+    pub created: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInstanceRequest {
+    pub region: String,
+    #[serde(rename = "type")]
+    pub instance_type: String,
+    pub image: String,
+    pub label: String,
+    pub tags: Vec<String>,
+    pub root_pass: String,
+    pub metadata: Metadata,
+    pub booted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Metadata {
+    /// Base64-encoded cloud-init user data served by the metadata service.
+    pub user_data: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Region {
+    pub id: String,
+    pub label: String,
+    pub country: String,
+    pub status: String,
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinodeType {
+    pub id: String,
+    pub label: String,
+    /// Memory in MB.
+    pub memory: u32,
+    pub vcpus: u32,
+    /// Disk in MB.
+    pub disk: u32,
+    /// Monthly transfer in GB.
+    pub transfer: u32,
+    pub price: Price,
+    #[serde(default)]
+    pub region_prices: Vec<RegionPrice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionPrice {
+    pub id: String,
+    pub monthly: f64,
+    pub hourly: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Price {
+    pub monthly: f64,
+    pub hourly: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Image {
+    pub id: String,
+    pub label: String,
+    pub vendor: Option<String>,
+    pub is_public: bool,
+    pub deprecated: bool,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+/// Linode wraps collections in a paginated envelope.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+}
+
+impl Default for CreateInstanceRequest {
+    fn default() -> Self {
+        Self {
+            region: "us-east".to_string(),
+            instance_type: "g6-nanode-1".to_string(), // Cheapest option
+            image: "linode/ubuntu24.04".to_string(),
+            label: String::new(),
+            tags: vec![],
+            root_pass: String::new(),
+            metadata: Metadata {
+                user_data: String::new(),
+            },
+            booted: true,
+        }
+    }
+}
@@ -1,4 +1,20 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub mod headscale;
+pub mod hetzner;
+pub mod lightsail;
+pub mod linode;
+pub mod tailscale;
+pub mod vultr;
+
+/// Tag (or label) attached to every server the tool creates.
+pub const SERVER_TAG: &str = "tailscale-vpn";
+
+/// Hours used to turn a monthly price into an hourly rate.
+pub const HOURS_PER_MONTH: f64 = 730.0;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DropletRequest {
@@ -8,6 +24,7 @@ pub struct DropletRequest {
     pub image: String,
     pub ssh_keys: Vec<String>,
     pub monitoring: bool,
+    pub ipv6: bool,
     pub tags: Vec<String>,
 }
 
@@ -19,11 +36,15 @@ pub struct Droplet {
     pub networks: Networks,
     pub region: Region,
     pub size: Size,
+    pub size_slug: String,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Networks {
     pub v4: Vec<NetworkV4>,
+    #[serde(default)]
+    pub v6: Vec<NetworkV6>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,10 +55,21 @@ pub struct NetworkV4 {
     pub network_type: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkV6 {
+    pub ip_address: String,
+    #[serde(rename = "type")]
+    pub network_type: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Region {
     pub name: String,
     pub slug: String,
+    #[serde(default)]
+    pub available: bool,
+    #[serde(default)]
+    pub sizes: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,7 +78,15 @@ pub struct Size {
     pub memory: u32,
     pub vcpus: u32,
     pub disk: u32,
+    #[serde(default)]
+    pub transfer: f64,
     pub price_monthly: f64,
+    #[serde(default)]
+    pub price_hourly: f64,
+    #[serde(default)]
+    pub regions: Vec<String>,
+    #[serde(default)]
+    pub available: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +94,34 @@ pub struct DropletResponse {
     pub droplet: Droplet,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DropletsResponse {
+    pub droplets: Vec<Droplet>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionsResponse {
+    pub regions: Vec<Region>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizesResponse {
+    pub sizes: Vec<Size>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Image {
+    pub id: u64,
+    pub slug: Option<String>,
+    pub distribution: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagesResponse {
+    pub images: Vec<Image>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     pub email: String,
@@ -67,6 +135,27 @@ pub struct AccountResponse {
     pub account: Account,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    DigitalOcean,
+    Hetzner,
+    Vultr,
+    Linode,
+    Lightsail,
+}
+
+/// The VPN software the server is set up with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VpnKind {
+    #[default]
+    Tailscale,
+    WireGuard,
+    OpenVpn,
+    Shadowsocks,
+}
+
 #[derive(Debug, Clone)]
 pub struct RegionOption {
     pub name: String,
@@ -74,57 +163,492 @@ pub struct RegionOption {
     pub description: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct SizeOption {
+    pub slug: String,
+    /// Memory in MB.
+    pub memory: u32,
+    pub vcpus: u32,
+    /// Disk in GB.
+    pub disk: u32,
+    /// Monthly transfer allowance in TB.
+    pub transfer: f64,
+    pub price_monthly: f64,
+    pub currency: &'static str,
+    /// Monthly prices for regions that differ from `price_monthly`.
+    pub region_prices: HashMap<String, f64>,
+    /// Region slugs offering this size; empty when it is offered everywhere.
+    pub regions: Vec<String>,
+}
+
+/// Linux distributions the setup script knows how to provision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distro {
+    Ubuntu,
+    Debian,
+    Fedora,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageOption {
+    /// Provider-specific image identifier passed back on create.
+    pub slug: String,
+    pub name: String,
+    pub distro: Distro,
+}
+
+/// Provider-agnostic description of the server to create.
+///
+/// `region`, `size` and `image` fall back to the provider's defaults when unset.
+#[derive(Debug, Clone)]
+pub struct ServerRequest {
+    pub name: String,
+    pub region: Option<String>,
+    pub size: Option<String>,
+    pub image: Option<String>,
+    pub user_data: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+/// A port the VPN listens on, for providers that firewall new servers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InboundPort {
+    pub port: u16,
+    pub protocol: Protocol,
+}
+
+/// A client config written to disk after a deployment.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientProfile {
+    pub name: String,
+    pub path: PathBuf,
+    /// The file's contents, for QR codes. Left out of JSON output since it
+    /// holds the client's secrets.
+    #[serde(skip)]
+    pub config: String,
+}
+
+/// How devices connect to a new server, depending on its VPN.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "vpn", rename_all = "lowercase")]
+pub enum VpnConnection {
+    Tailscale {
+        tailnet_hostname: String,
+        exit_node: ExitNodeStatus,
+        /// Headscale URL the server joined; `None` for tailscale.com.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        login_server: Option<String>,
+    },
+    WireGuard {
+        clients: Vec<ClientProfile>,
+    },
+    #[serde(rename = "openvpn")]
+    OpenVpn {
+        clients: Vec<ClientProfile>,
+    },
+    Shadowsocks {
+        /// Each client's `config` is its `ss://` access URL.
+        clients: Vec<ClientProfile>,
+    },
+}
+
+/// Whether the new server can be used as an exit node straight away.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ExitNodeStatus {
+    /// The exit node routes were approved through the control server API.
+    Approved,
+    /// Approving failed, so the routes still need approval in the admin console.
+    ApprovalFailed { error: String },
+    /// No control server API credentials were available to approve the routes.
+    NeedsApproval,
+}
+
 #[derive(Debug, Clone)]
 pub struct ServerInfo {
     pub name: String,
+    /// Region slug, which tells where the provider default put the server.
+    pub region: String,
     pub ip: String,
+    pub ipv6: Option<String>,
+}
+
+/// A server created by this tool, as currently reported by the provider.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeployedServer {
+    pub id: String,
+    pub name: String,
+    pub region: String,
+    pub size: String,
+    pub status: String,
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The servers a provider listed.
+#[derive(Debug, Clone, Default)]
+pub struct ServerList {
+    pub servers: Vec<DeployedServer>,
+    /// Regions that could not be listed, for providers asked region by
+    /// region; their servers are missing from `servers`.
+    pub failed_regions: Vec<String>,
+}
+
+impl ServerList {
+    /// Warning to show when some regions could not be listed.
+    pub fn warning(&self) -> Option<String> {
+        (!self.failed_regions.is_empty()).then(|| {
+            format!(
+                "Could not list servers in {}; they are not shown",
+                self.failed_regions.join(", ")
+            )
+        })
+    }
+}
+
+impl FromIterator<DeployedServer> for ServerList {
+    fn from_iter<I: IntoIterator<Item = DeployedServer>>(servers: I) -> Self {
+        Self {
+            servers: servers.into_iter().collect(),
+            failed_regions: Vec::new(),
+        }
+    }
+}
+
+/// A node in the tailnet, as reported by the coordination server.
+#[derive(Debug, Clone)]
+pub struct TailnetNode {
+    pub id: String,
+    /// Name the node is known by in the tailnet, e.g. its MagicDNS name.
+    pub name: String,
+    /// Hostname the node registered with.
+    pub hostname: String,
+    pub online: bool,
+    /// Whether the node may use the tailnet yet, on tailnets that require
+    /// device approval.
+    pub authorized: bool,
+    pub advertised_routes: Vec<String>,
+    pub enabled_routes: Vec<String>,
 }
 
 impl Default for DropletRequest {
     fn default() -> Self {
         Self {
-            name: format!(
-                "tailscale-vpn-{}",
-                uuid::Uuid::new_v4().to_string()[..8].to_string()
-            ),
+            name: generate_server_name(),
             region: "nyc1".to_string(),
             size: "s-1vcpu-512mb-10gb".to_string(), // Cheapest option
             image: "ubuntu-24-04-x64".to_string(),
             ssh_keys: vec![],
             monitoring: true,
-            tags: vec!["tailscale-vpn".to_string()],
-        }
-    }
-}
-
-impl RegionOption {
-    pub fn available_regions() -> Vec<RegionOption> {
-        vec![
-            RegionOption {
-                name: "New York".to_string(),
-                slug: "nyc1".to_string(),
-                description: "NYC - United States".to_string(),
-            },
-            RegionOption {
-                name: "San Francisco".to_string(),
-                slug: "sfo3".to_string(),
-                description: "SF - United States".to_string(),
-            },
-            RegionOption {
-                name: "Amsterdam".to_string(),
-                slug: "ams3".to_string(),
-                description: "AMS - Netherlands".to_string(),
-            },
-            RegionOption {
-                name: "Singapore".to_string(),
-                slug: "sgp1".to_string(),
-                description: "SG - Singapore".to_string(),
-            },
-            RegionOption {
-                name: "Sydney".to_string(),
-                slug: "syd1".to_string(),
-                description: "SYD - Australia".to_string(),
-            },
+            ipv6: true,
+            tags: vec![SERVER_TAG.to_string()],
+        }
+    }
+}
+
+impl ProviderKind {
+    pub fn all() -> &'static [ProviderKind] {
+        &[
+            ProviderKind::DigitalOcean,
+            ProviderKind::Hetzner,
+            ProviderKind::Vultr,
+            ProviderKind::Linode,
+            ProviderKind::Lightsail,
+        ]
+    }
+
+    /// Identifier used on the command line.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            ProviderKind::DigitalOcean => "digitalocean",
+            ProviderKind::Hetzner => "hetzner",
+            ProviderKind::Vultr => "vultr",
+            ProviderKind::Linode => "linode",
+            ProviderKind::Lightsail => "lightsail",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<ProviderKind> {
+        ProviderKind::all()
+            .iter()
+            .copied()
+            .find(|kind| kind.slug() == slug.to_lowercase())
+    }
+
+    /// Environment variable holding the API token in headless mode.
+    pub fn token_env_var(&self) -> Option<&'static str> {
+        match self {
+            ProviderKind::DigitalOcean => Some("DIGITALOCEAN_TOKEN"),
+            ProviderKind::Hetzner => Some("HCLOUD_TOKEN"),
+            ProviderKind::Vultr => Some("VULTR_API_KEY"),
+            ProviderKind::Linode => Some("LINODE_TOKEN"),
+            ProviderKind::Lightsail => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ProviderKind::DigitalOcean => "DigitalOcean",
+            ProviderKind::Hetzner => "Hetzner Cloud",
+            ProviderKind::Vultr => "Vultr",
+            ProviderKind::Linode => "Linode (Akamai)",
+            ProviderKind::Lightsail => "AWS Lightsail",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ProviderKind::DigitalOcean => "Droplets from $4/month in the US, Europe and Asia",
+            ProviderKind::Hetzner => "Servers from ~€4/month in Germany, Finland and the US",
+            ProviderKind::Vultr => "Instances from $5/month incl. Johannesburg and São Paulo",
+            ProviderKind::Linode => "Nanodes from $5/month across Akamai's global regions",
+            ProviderKind::Lightsail => "Instances from $5/month using your existing AWS account",
+        }
+    }
+
+    pub fn console_url(&self) -> &'static str {
+        match self {
+            ProviderKind::DigitalOcean => "cloud.digitalocean.com",
+            ProviderKind::Hetzner => "console.hetzner.cloud",
+            ProviderKind::Vultr => "my.vultr.com",
+            ProviderKind::Linode => "cloud.linode.com",
+            ProviderKind::Lightsail => "lightsail.aws.amazon.com",
+        }
+    }
+
+    /// Whether the auth screen must be filled in. Lightsail picks up the
+    /// standard AWS credentials, so its input is an optional profile name.
+    pub fn requires_token(&self) -> bool {
+        !matches!(self, ProviderKind::Lightsail)
+    }
+}
+
+impl VpnKind {
+    pub fn all() -> &'static [VpnKind] {
+        &[
+            VpnKind::Tailscale,
+            VpnKind::WireGuard,
+            VpnKind::OpenVpn,
+            VpnKind::Shadowsocks,
         ]
     }
+
+    /// Identifier used on the command line.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            VpnKind::Tailscale => "tailscale",
+            VpnKind::WireGuard => "wireguard",
+            VpnKind::OpenVpn => "openvpn",
+            VpnKind::Shadowsocks => "shadowsocks",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<VpnKind> {
+        VpnKind::all()
+            .iter()
+            .copied()
+            .find(|kind| kind.slug() == slug.to_lowercase())
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            VpnKind::Tailscale => "Tailscale",
+            VpnKind::WireGuard => "WireGuard",
+            VpnKind::OpenVpn => "OpenVPN",
+            VpnKind::Shadowsocks => "Shadowsocks",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            VpnKind::Tailscale => "Exit node in your tailnet, no ports exposed to the internet",
+            VpnKind::WireGuard => "Plain WireGuard with client configs for any WireGuard app",
+            VpnKind::OpenVpn => "OpenVPN with .ovpn profiles, for devices limited to OpenVPN",
+            VpnKind::Shadowsocks => "Obfuscated proxy with ss:// access URLs, works with Outline",
+        }
+    }
+}
+
+impl VpnConnection {
+    /// Client configs, or access URLs, that fit in a QR code. OpenVPN
+    /// profiles carry their certificates, which makes them too large to scan.
+    pub fn qr_clients(&self) -> &[ClientProfile] {
+        match self {
+            VpnConnection::WireGuard { clients } | VpnConnection::Shadowsocks { clients } => {
+                clients
+            }
+            VpnConnection::Tailscale { .. } | VpnConnection::OpenVpn { .. } => &[],
+        }
+    }
+}
+
+impl ExitNodeStatus {
+    pub fn summary(&self) -> String {
+        match self {
+            ExitNodeStatus::Approved => "approved".to_string(),
+            ExitNodeStatus::ApprovalFailed { error } => {
+                format!(
+                    "approval failed ({}); approve it in the admin console",
+                    error
+                )
+            }
+            ExitNodeStatus::NeedsApproval => "needs approval on the control server".to_string(),
+        }
+    }
+}
+
+impl Protocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Udp => "udp",
+        }
+    }
+}
+
+impl SizeOption {
+    pub fn is_available_in(&self, region: &str) -> bool {
+        self.regions.is_empty() || self.regions.iter().any(|r| r == region)
+    }
+
+    pub fn price_in(&self, region: &str) -> f64 {
+        self.region_prices
+            .get(region)
+            .copied()
+            .unwrap_or(self.price_monthly)
+    }
+
+    /// Approximate amount billed for running `hours` in `region`.
+    pub fn accumulated_cost(&self, region: &str, hours: f64) -> f64 {
+        self.price_in(region) * hours / HOURS_PER_MONTH
+    }
+
+    pub fn format_price(&self, price: f64) -> String {
+        format!("{}{:.2}/month", self.currency, price)
+    }
+
+    pub fn specs(&self) -> String {
+        let memory = if self.memory >= 1024 {
+            format!("{}GB", self.memory / 1024)
+        } else {
+            format!("{}MB", self.memory)
+        };
+        format!(
+            "{} RAM, {} vCPU, {}GB disk, {}TB transfer",
+            memory, self.vcpus, self.disk, self.transfer
+        )
+    }
+}
+
+impl Distro {
+    /// Maps a provider's distribution or OS family name to a supported distro.
+    pub fn from_name(name: &str) -> Option<Distro> {
+        let name = name.to_lowercase();
+        if name.contains("ubuntu") {
+            Some(Distro::Ubuntu)
+        } else if name.contains("debian") {
+            Some(Distro::Debian)
+        } else if name.contains("fedora") {
+            Some(Distro::Fedora)
+        } else {
+            None
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Distro::Ubuntu => "Ubuntu",
+            Distro::Debian => "Debian",
+            Distro::Fedora => "Fedora",
+        }
+    }
+}
+
+impl ImageOption {
+    /// Builds an image option if `distribution` and `version` describe a
+    /// supported release: Ubuntu 22.04/24.04 LTS, Debian 12 or newer, or Fedora.
+    pub fn supported(slug: String, distribution: &str, version: &str) -> Option<ImageOption> {
+        let distro = Distro::from_name(distribution)?;
+        let normalized = version.replace(['_', '-'], ".");
+
+        let supported = match distro {
+            Distro::Ubuntu => normalized.contains("22.04") || normalized.contains("24.04"),
+            Distro::Debian => normalized
+                .split(|c: char| !c.is_ascii_digit())
+                .find(|part| !part.is_empty())
+                .and_then(|major| major.parse::<u32>().ok())
+                .is_some_and(|major| major >= 12),
+            Distro::Fedora => true,
+        };
+
+        // Some providers already include the distro in the version label
+        let version = version.trim();
+        let name = if Distro::from_name(version) == Some(distro) {
+            version.to_string()
+        } else {
+            format!("{} {}", distro.display_name(), version)
+        };
+
+        supported.then_some(ImageOption { slug, name, distro })
+    }
+}
+
+impl DeployedServer {
+    /// Human-readable time since creation, e.g. `3d 4h`.
+    pub fn age(&self) -> String {
+        format_age(self.created_at)
+    }
+
+    pub fn hours_running(&self) -> f64 {
+        hours_since(self.created_at)
+    }
+}
+
+impl TailnetNode {
+    /// Whether the node offers itself as an exit node.
+    pub fn advertises_exit_node(&self) -> bool {
+        self.advertised_routes
+            .iter()
+            .any(|route| route == "0.0.0.0/0")
+    }
+}
+
+impl ServerRequest {
+    pub fn new(name: String, user_data: String) -> Self {
+        Self {
+            name,
+            region: None,
+            size: None,
+            image: None,
+            user_data,
+            tags: vec![SERVER_TAG.to_string()],
+        }
+    }
+}
+
+/// Human-readable time since `created_at`, e.g. `3d 4h`.
+pub fn format_age(created_at: DateTime<Utc>) -> String {
+    let age = Utc::now().signed_duration_since(created_at);
+    if age.num_days() > 0 {
+        format!("{}d {}h", age.num_days(), age.num_hours() % 24)
+    } else if age.num_hours() > 0 {
+        format!("{}h {}m", age.num_hours(), age.num_minutes() % 60)
+    } else {
+        format!("{}m", age.num_minutes().max(0))
+    }
+}
+
+pub fn hours_since(created_at: DateTime<Utc>) -> f64 {
+    let age = Utc::now().signed_duration_since(created_at);
+    age.num_seconds().max(0) as f64 / 3600.0
+}
+
+pub fn generate_server_name() -> String {
+    format!("{}-{}", SERVER_TAG, &uuid::Uuid::new_v4().to_string()[..8])
 }
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Device {
+    pub id: String,
+    /// Fully qualified MagicDNS name, e.g. `host.tailnet-abc.ts.net`.
+    pub name: String,
+    pub hostname: String,
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    /// Whether the node currently has a connection to the coordination server.
+    #[serde(default)]
+    pub connected_to_control: bool,
+    /// `false` until approved on tailnets that require device approval.
+    #[serde(default)]
+    pub authorized: bool,
+    pub last_seen: Option<DateTime<Utc>>,
+    /// Subnet and exit node routes the node offers; only returned with `fields=all`.
+    #[serde(default)]
+    pub advertised_routes: Vec<String>,
+    #[serde(default)]
+    pub enabled_routes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevicesResponse {
+    pub devices: Vec<Device>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceRoutes {
+    #[serde(default)]
+    pub advertised_routes: Vec<String>,
+    #[serde(default)]
+    pub enabled_routes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAuthKeyRequest {
+    pub capabilities: KeyCapabilities,
+    pub expiry_seconds: u64,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyCapabilities {
+    pub devices: DeviceCapabilities,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceCapabilities {
+    pub create: DeviceCreateCapabilities,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceCreateCapabilities {
+    pub reusable: bool,
+    pub ephemeral: bool,
+    pub preauthorized: bool,
+    /// ACL tags applied to devices joining with the key; required when the
+    /// key is created with an OAuth client.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuthKey {
+    /// The secret itself; only returned when the key is created.
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthTokenResponse {
+    pub access_token: String,
+}
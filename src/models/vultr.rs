@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Instance {
+    pub id: String,
+    pub label: String,
+    pub status: String,
+    pub power_status: String,
+    pub main_ip: String,
+    #[serde(default)]
+    pub v6_main_ip: String,
+    pub region: String,
+    pub plan: String,
+    pub date_created: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceResponse {
+    pub instance: Instance,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstancesResponse {
+    pub instances: Vec<Instance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInstanceRequest {
+    pub region: String,
+    pub plan: String,
+    pub os_id: u32,
+    pub label: String,
+    pub hostname: String,
+    pub tags: Vec<String>,
+    /// Base64-encoded cloud-init user data.
+    pub user_data: String,
+    pub enable_ipv6: bool,
+    pub backups: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Region {
+    pub id: String,
+    pub city: String,
+    pub country: String,
+    pub continent: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionsResponse {
+    pub regions: Vec<Region>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plan {
+    pub id: String,
+    pub vcpu_count: u32,
+    /// Memory in MB.
+    pub ram: u32,
+    /// Disk in GB.
+    pub disk: u32,
+    /// Monthly bandwidth in GB.
+    pub bandwidth: u32,
+    pub monthly_cost: f64,
+    pub locations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlansResponse {
+    pub plans: Vec<Plan>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatingSystem {
+    pub id: u32,
+    pub name: String,
+    pub arch: String,
+    pub family: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperatingSystemsResponse {
+    pub os: Vec<OperatingSystem>,
+}
+
+impl Default for CreateInstanceRequest {
+    fn default() -> Self {
+        Self {
+            region: "ewr".to_string(),
+            plan: "vc2-1c-1gb".to_string(), // Cheapest plan offered in every region
+            os_id: 2284,                    // Ubuntu 24.04 LTS x64
+            label: String::new(),
+            hostname: String::new(),
+            tags: vec![],
+            user_data: String::new(),
+            enable_ipv6: true,
+            backups: "disabled".to_string(),
+        }
+    }
+}
@@ -0,0 +1,847 @@
+//! Cloud-init user data for the VPN servers, built as a typed cloud-config
+//! document and serialized to YAML.
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::models::Distro;
+
+/// Revision of the generated script, recorded with each deployment. Bump it
+/// whenever the script changes so older servers can be told apart.
+pub const CLOUD_INIT_VERSION: u32 = 4;
+
+/// Release of shadowsocks-rust installed on Shadowsocks servers.
+const SHADOWSOCKS_RUST_VERSION: &str = "v1.21.2";
+
+/// The subset of cloud-config the setup scripts use.
+///
+/// Modules run in cloud-init's order regardless of field order: files are
+/// written and users created first, then packages are installed, and
+/// `runcmd` runs last.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CloudConfig {
+    /// Refresh the package index before installing `packages`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub package_update: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<User>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub write_files: Vec<WriteFile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runcmd: Vec<Command>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_message: Option<String>,
+}
+
+/// Entries added to every generated cloud-config, read from the file passed
+/// with `--cloud-init-extra`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CloudConfigExtra {
+    #[serde(default)]
+    pub packages: Vec<String>,
+    #[serde(default)]
+    pub write_files: Vec<WriteFile>,
+    #[serde(default)]
+    pub runcmd: Vec<Command>,
+}
+
+/// An entry of `users`. Listing any user replaces the distro's default one
+/// unless `default` is listed too.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum User {
+    /// A user cloud-init already knows about, i.e. `default`.
+    Named(String),
+    Account {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        groups: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sudo: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shell: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        ssh_authorized_keys: Vec<String>,
+    },
+}
+
+/// An entry of `write_files`. Other keys (`encoding`, `append`, ...) are
+/// rejected rather than dropped, since ignoring them would change the file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WriteFile {
+    pub path: String,
+    pub content: String,
+    /// Octal mode as a string, e.g. `"0600"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+}
+
+/// An entry of `runcmd`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Command {
+    /// Run with `sh -c`, so it may use pipes and redirection.
+    Shell(String),
+    /// Run directly, without a shell.
+    Exec(Vec<String>),
+}
+
+impl CloudConfig {
+    /// Adds `extra`'s entries after the generated ones, so its commands run
+    /// once the VPN is set up. Files the setup writes can't be replaced.
+    pub fn merge(&mut self, extra: &CloudConfigExtra) -> Result<()> {
+        if let Some(file) = extra
+            .write_files
+            .iter()
+            .find(|file| self.write_files.iter().any(|own| own.path == file.path))
+        {
+            return Err(anyhow!(
+                "--cloud-init-extra cannot replace {}, which the VPN setup writes",
+                file.path
+            ));
+        }
+
+        for package in &extra.packages {
+            if !self.packages.contains(package) {
+                self.packages.push(package.clone());
+            }
+        }
+        self.package_update |= !extra.packages.is_empty();
+        self.write_files.extend(extra.write_files.iter().cloned());
+        self.runcmd.extend(extra.runcmd.iter().cloned());
+        Ok(())
+    }
+
+    /// The user data to pass to the provider.
+    pub fn to_user_data(&self) -> Result<String> {
+        let yaml = serde_yaml::to_string(self)
+            .map_err(|e| anyhow!("Failed to serialize cloud-config: {}", e))?;
+        Ok(format!("#cloud-config\n{}", yaml))
+    }
+
+    /// The same setup as a shell script, for providers that run user data
+    /// as a launch script rather than handing it to cloud-init. Steps run in
+    /// cloud-init's module order and, as with `runcmd`, a failing command
+    /// does not stop the ones after it.
+    pub fn to_launch_script(&self) -> String {
+        let mut script = vec!["#!/bin/sh".to_string()];
+
+        for file in &self.write_files {
+            script.push(write_file_commands(file));
+        }
+
+        for user in &self.users {
+            // The distro's default user already exists
+            if let User::Account { .. } = user {
+                script.push(user_commands(user));
+            }
+        }
+
+        if self.package_update || !self.packages.is_empty() {
+            let packages = self.packages.join(" ");
+            let (apt_update, dnf_update) = if self.package_update {
+                ("\n  apt-get update", "\n  dnf makecache")
+            } else {
+                ("", "")
+            };
+            let (apt_install, dnf_install) = if self.packages.is_empty() {
+                (String::new(), String::new())
+            } else {
+                (
+                    format!("\n  apt-get install -y {}", packages),
+                    format!("\n  dnf install -y {}", packages),
+                )
+            };
+            script.push(format!(
+                "if command -v apt-get >/dev/null 2>&1; then\n  \
+                 export DEBIAN_FRONTEND=noninteractive{}{}\n\
+                 else{}{}\n\
+                 fi",
+                apt_update, apt_install, dnf_update, dnf_install
+            ));
+        }
+
+        for command in &self.runcmd {
+            script.push(match command {
+                Command::Shell(line) => line.clone(),
+                Command::Exec(args) => args
+                    .iter()
+                    .map(|arg| shell_quote(arg))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            });
+        }
+
+        if let Some(message) = &self.final_message {
+            script.push(format!("echo {}", shell_quote(message)));
+        }
+
+        script.join("\n") + "\n"
+    }
+}
+
+impl CloudConfigExtra {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&contents).map_err(|e| anyhow!("Invalid {}: {}", path.display(), e))
+    }
+
+    fn parse(contents: &str) -> Result<Self> {
+        // An empty file (or one holding only the #cloud-config header) adds nothing
+        if contents.lines().all(|line| {
+            let line = line.trim();
+            line.is_empty() || line.starts_with('#')
+        }) {
+            return Ok(Self::default());
+        }
+        Ok(serde_yaml::from_str(contents)?)
+    }
+}
+
+impl WriteFile {
+    pub fn new(path: &str, content: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+
+    /// A file only root can read, for configs holding secrets.
+    pub fn private(path: &str, content: impl Into<String>) -> Self {
+        Self {
+            permissions: Some("0600".to_string()),
+            ..Self::new(path, content)
+        }
+    }
+
+    pub fn executable(path: &str, content: impl Into<String>) -> Self {
+        Self {
+            permissions: Some("0755".to_string()),
+            ..Self::new(path, content)
+        }
+    }
+}
+
+impl Command {
+    pub fn shell(command: impl Into<String>) -> Self {
+        Command::Shell(command.into())
+    }
+
+    pub fn exec(args: &[&str]) -> Self {
+        Command::Exec(args.iter().map(|arg| arg.to_string()).collect())
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// Quotes `value` as a single shell word, unless it already is one.
+fn shell_quote(value: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./=@:,+%".contains(c);
+    if !value.is_empty() && value.chars().all(plain) {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Writes `file` like the `write_files` module: created private, then
+/// given its mode (0644 unless set) and owner. The content is base64 encoded
+/// so nothing in it can end the here-document early.
+fn write_file_commands(file: &WriteFile) -> String {
+    let path = shell_quote(&file.path);
+    let encoded = STANDARD.encode(&file.content);
+    let mut lines = vec![
+        format!("mkdir -p \"$(dirname {})\"", path),
+        format!("(umask 077 && base64 -d > {}) <<'EOF'", path),
+    ];
+    lines.extend(
+        encoded
+            .as_bytes()
+            .chunks(76)
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned()),
+    );
+    lines.push("EOF".to_string());
+    lines.push(format!(
+        "chmod {} {}",
+        file.permissions.as_deref().unwrap_or("0644"),
+        path
+    ));
+    if let Some(owner) = &file.owner {
+        lines.push(format!("chown {} {}", shell_quote(owner), path));
+    }
+    lines.join("\n")
+}
+
+/// Creates a `User::Account` like the `users_groups` module.
+fn user_commands(user: &User) -> String {
+    let User::Account {
+        name,
+        groups,
+        sudo,
+        shell,
+        ssh_authorized_keys,
+    } = user
+    else {
+        return String::new();
+    };
+    let quoted = shell_quote(name);
+
+    let mut useradd = vec!["useradd --create-home".to_string()];
+    if let Some(groups) = groups {
+        useradd.push(format!(
+            "--groups {}",
+            shell_quote(&groups.replace(' ', ""))
+        ));
+    }
+    if let Some(shell) = shell {
+        useradd.push(format!("--shell {}", shell_quote(shell)));
+    }
+    useradd.push(quoted.clone());
+    let mut lines = vec![format!(
+        "id -u {} >/dev/null 2>&1 || {}",
+        quoted,
+        useradd.join(" ")
+    )];
+
+    if let Some(sudo) = sudo {
+        let sudoers = shell_quote(&format!("/etc/sudoers.d/90-{}", name));
+        lines.push(format!(
+            "echo {} > {} && chmod 0440 {}",
+            shell_quote(&format!("{} {}", name, sudo)),
+            sudoers,
+            sudoers
+        ));
+    }
+    if !ssh_authorized_keys.is_empty() {
+        lines.push(format!(
+            "ssh_dir=\"$(getent passwd {} | cut -d: -f6)/.ssh\"",
+            quoted
+        ));
+        lines.push(format!(
+            "install -d -m 0700 -o {} -g {} \"$ssh_dir\"",
+            quoted, quoted
+        ));
+        for key in ssh_authorized_keys {
+            lines.push(format!(
+                "echo {} >> \"$ssh_dir/authorized_keys\"",
+                shell_quote(key)
+            ));
+        }
+        lines.push(format!(
+            "chmod 0600 \"$ssh_dir/authorized_keys\" && chown {} \"$ssh_dir/authorized_keys\"",
+            quoted
+        ));
+    }
+    lines.join("\n")
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+/// Enables IPv4 and IPv6 forwarding, persisted in `/etc/sysctl.d/<name>`.
+fn ip_forwarding(name: &str) -> (WriteFile, Command) {
+    let path = format!("/etc/sysctl.d/{}", name);
+    (
+        WriteFile::new(
+            &path,
+            "net.ipv4.ip_forward = 1\nnet.ipv6.conf.all.forwarding = 1\n",
+        ),
+        Command::exec(&["sysctl", "-p", &path]),
+    )
+}
+
+/// `hostname` is the name the server registers in the tailnet under, and
+/// `login_server` the Headscale URL to join instead of tailscale.com.
+pub fn tailscale_cloud_config(
+    auth_key: &str,
+    hostname: &str,
+    login_server: Option<&str>,
+) -> CloudConfig {
+    let login_server = login_server
+        .map(|url| format!(" --login-server={}", url))
+        .unwrap_or_default();
+    let (sysctl_file, apply_sysctl) = ip_forwarding("99-tailscale.conf");
+
+    CloudConfig {
+        package_update: true,
+        packages: strings(&["curl", "wget"]),
+        write_files: vec![sysctl_file],
+        runcmd: vec![
+            Command::shell("curl -fsSL https://tailscale.com/install.sh | sh"),
+            apply_sysctl,
+            // Clean up any existing machine identity
+            // See: https://github.com/tailscale/tailscale/issues/9382
+            Command::exec(&["systemctl", "stop", "tailscaled"]),
+            Command::shell("rm -rf /var/lib/tailscale/* || true"),
+            // Start the daemon with clean state and give it time to be ready
+            Command::exec(&["systemctl", "enable", "tailscaled"]),
+            Command::exec(&["systemctl", "start", "tailscaled"]),
+            Command::exec(&["sleep", "10"]),
+            // `sh` is dash on Debian and Ubuntu, so count with seq rather
+            // than brace expansion. Generated keys are single-use, so stop as
+            // soon as the node is logged in, even if `up` itself failed.
+            Command::shell(format!(
+                "for i in $(seq 1 10); do \
+                 if tailscale status >/dev/null 2>&1; then \
+                 echo \"Tailscale logged in before attempt $i\"; break; fi; \
+                 if tailscale up --reset --force-reauth --auth-key={} --hostname={}{} \
+                 --accept-routes --advertise-exit-node; then \
+                 echo \"Tailscale connected successfully on attempt $i\"; break; \
+                 else echo \"Attempt $i failed, retrying in 1 second...\"; sleep 1; fi; \
+                 done",
+                auth_key, hostname, login_server
+            )),
+            // Applies the settings again in case `up` logged in but failed
+            // before it got to them
+            Command::shell(format!(
+                "tailscale set --ssh --hostname={} --accept-routes --advertise-exit-node",
+                hostname
+            )),
+            Command::shell(
+                "echo \"SUCCESS: Tailscale connected at $(date)\" > /var/log/tailscale-success.log",
+            ),
+            Command::shell("tailscale status >> /var/log/tailscale-success.log 2>&1"),
+        ],
+        final_message: Some("Cloud-init complete. Tailscale setup finished.".to_string()),
+        ..Default::default()
+    }
+}
+
+/// Sets the server up with `server_config` as `/etc/wireguard/wg0.conf`.
+pub fn wireguard_cloud_config(server_config: &str, distro: Distro) -> CloudConfig {
+    let packages: &[&str] = match distro {
+        Distro::Ubuntu | Distro::Debian => &["wireguard", "iptables"],
+        Distro::Fedora => &["wireguard-tools", "iptables"],
+    };
+    let (sysctl_file, apply_sysctl) = ip_forwarding("99-wireguard.conf");
+
+    CloudConfig {
+        package_update: true,
+        packages: strings(packages),
+        write_files: vec![
+            WriteFile::private("/etc/wireguard/wg0.conf", server_config),
+            sysctl_file,
+        ],
+        runcmd: vec![
+            apply_sysctl,
+            // Bring up the tunnel now and on every boot
+            Command::exec(&["systemctl", "enable", "--now", "wg-quick@wg0"]),
+            Command::shell(
+                "echo \"SUCCESS: WireGuard started at $(date)\" > /var/log/wireguard-success.log",
+            ),
+            Command::shell("wg show >> /var/log/wireguard-success.log 2>&1"),
+        ],
+        final_message: Some("Cloud-init complete. WireGuard setup finished.".to_string()),
+        ..Default::default()
+    }
+}
+
+/// Sets the server up with `server_config` as an OpenVPN server, and
+/// `nat_script` run at boot to route its clients to the internet.
+pub fn openvpn_cloud_config(server_config: &str, nat_script: &str) -> CloudConfig {
+    let (sysctl_file, apply_sysctl) = ip_forwarding("99-openvpn.conf");
+
+    CloudConfig {
+        package_update: true,
+        packages: strings(&["openvpn", "iptables"]),
+        write_files: vec![
+            WriteFile::private("/etc/openvpn/server/server.conf", server_config),
+            WriteFile::executable("/usr/local/sbin/vpn-nat", nat_script),
+            WriteFile::new(
+                "/etc/systemd/system/vpn-nat.service",
+                "[Unit]\n\
+                 Description=NAT for VPN clients\n\
+                 After=network-online.target\n\
+                 Wants=network-online.target\n\
+                 \n\
+                 [Service]\n\
+                 Type=oneshot\n\
+                 RemainAfterExit=yes\n\
+                 ExecStart=/usr/local/sbin/vpn-nat -A\n\
+                 ExecStop=/usr/local/sbin/vpn-nat -D\n\
+                 \n\
+                 [Install]\n\
+                 WantedBy=multi-user.target\n",
+            ),
+            sysctl_file,
+        ],
+        runcmd: vec![
+            apply_sysctl,
+            // Start NAT and the server now and on every boot
+            Command::exec(&["systemctl", "daemon-reload"]),
+            Command::exec(&["systemctl", "enable", "--now", "vpn-nat"]),
+            Command::exec(&["systemctl", "enable", "--now", "openvpn-server@server"]),
+            Command::shell(
+                "echo \"SUCCESS: OpenVPN started at $(date)\" > /var/log/openvpn-success.log",
+            ),
+            Command::shell(
+                "systemctl status openvpn-server@server >> /var/log/openvpn-success.log 2>&1",
+            ),
+        ],
+        final_message: Some("Cloud-init complete. OpenVPN setup finished.".to_string()),
+        ..Default::default()
+    }
+}
+
+/// Sets the server up with `server_config` as the config of a
+/// shadowsocks-rust server. The release is checked against its published
+/// checksum, since no distro packages it everywhere.
+pub fn shadowsocks_cloud_config(server_config: &str, distro: Distro) -> CloudConfig {
+    let packages: &[&str] = match distro {
+        Distro::Ubuntu | Distro::Debian => &["curl", "tar", "xz-utils"],
+        Distro::Fedora => &["curl", "tar", "xz"],
+    };
+    let install_script = format!(
+        "#!/bin/sh\n\
+         set -eu\n\
+         version={}\n\
+         asset=\"shadowsocks-$version.$(uname -m)-unknown-linux-gnu.tar.xz\"\n\
+         url=\"https://github.com/shadowsocks/shadowsocks-rust/releases/download/$version/$asset\"\n\
+         cd \"$(mktemp -d)\"\n\
+         curl -fsSLO \"$url\"\n\
+         curl -fsSLO \"$url.sha256\"\n\
+         sha256sum -c \"$asset.sha256\"\n\
+         tar -xJf \"$asset\" -C /usr/local/bin ssserver\n",
+        SHADOWSOCKS_RUST_VERSION
+    );
+
+    CloudConfig {
+        package_update: true,
+        packages: strings(packages),
+        write_files: vec![
+            WriteFile::private("/etc/shadowsocks-rust/config.json", server_config),
+            WriteFile::executable("/usr/local/sbin/install-shadowsocks", install_script),
+            WriteFile::new(
+                "/etc/systemd/system/shadowsocks.service",
+                "[Unit]\n\
+                 Description=Shadowsocks server\n\
+                 After=network-online.target\n\
+                 Wants=network-online.target\n\
+                 \n\
+                 [Service]\n\
+                 DynamicUser=yes\n\
+                 LoadCredential=config.json:/etc/shadowsocks-rust/config.json\n\
+                 ExecStart=/usr/local/bin/ssserver -c ${CREDENTIALS_DIRECTORY}/config.json\n\
+                 Restart=on-failure\n\
+                 LimitNOFILE=51200\n\
+                 \n\
+                 [Install]\n\
+                 WantedBy=multi-user.target\n",
+            ),
+        ],
+        runcmd: vec![
+            // Install the server and start it now and on every boot
+            Command::exec(&["/usr/local/sbin/install-shadowsocks"]),
+            Command::exec(&["systemctl", "daemon-reload"]),
+            Command::exec(&["systemctl", "enable", "--now", "shadowsocks"]),
+            Command::shell(
+                "echo \"SUCCESS: Shadowsocks started at $(date)\" > /var/log/shadowsocks-success.log",
+            ),
+            Command::shell("systemctl status shadowsocks >> /var/log/shadowsocks-success.log 2>&1"),
+        ],
+        final_message: Some("Cloud-init complete. Shadowsocks setup finished.".to_string()),
+        ..Default::default()
+    }
+}
+
+/// Shell commands adding (`-A`) or deleting (`-D`) the rules that forward
+/// traffic from `tunnel` and masquerade it out of the default route's
+/// interface. IPv6 NAT is best effort, since not every server has IPv6.
+pub fn nat_rules(action: &str, tunnel: &str, subnet_v4: &str, subnet_v6: &str) -> String {
+    let uplink = "$(ip -4 route show default | awk '{print $5; exit}')";
+    format!(
+        "iptables {action} FORWARD -i {tunnel} -j ACCEPT; \
+         iptables -t nat {action} POSTROUTING -s {v4} -o {uplink} -j MASQUERADE; \
+         ip6tables -t nat {action} POSTROUTING -s {v6} -o {uplink} -j MASQUERADE || true",
+        action = action,
+        tunnel = tunnel,
+        v4 = subnet_v4,
+        v6 = subnet_v6,
+        uplink = uplink,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(user_data: &str) -> CloudConfig {
+        let yaml = user_data
+            .strip_prefix("#cloud-config\n")
+            .expect("user data starts with the cloud-config header");
+        serde_yaml::from_str(yaml).expect("user data is valid YAML")
+    }
+
+    fn all_configs() -> Vec<CloudConfig> {
+        vec![
+            tailscale_cloud_config("tskey-auth-abc", "tailscale-vpn-1", None),
+            wireguard_cloud_config("[Interface]\nListenPort = 51820\n", Distro::Debian),
+            openvpn_cloud_config("port 1194\nproto udp\n", "#!/bin/sh\nexit 0\n"),
+            shadowsocks_cloud_config("{\n  \"mode\": \"tcp_and_udp\"\n}", Distro::Fedora),
+        ]
+    }
+
+    #[test]
+    fn user_data_round_trips() {
+        for config in all_configs() {
+            let user_data = config.to_user_data().unwrap();
+            assert_eq!(parse(&user_data), config);
+        }
+    }
+
+    #[test]
+    fn user_data_has_top_level_modules() {
+        let user_data = tailscale_cloud_config("tskey-auth-abc", "tailscale-vpn-1", None)
+            .to_user_data()
+            .unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&user_data).unwrap();
+
+        assert_eq!(value["package_update"], serde_yaml::Value::Bool(true));
+        assert!(value["packages"].is_sequence());
+        assert!(value["write_files"].is_sequence());
+        assert!(value["runcmd"].is_sequence());
+        assert!(value["final_message"].is_string());
+        // Empty modules are left out rather than serialized as null
+        assert!(value.get("users").is_none());
+    }
+
+    #[test]
+    fn permissions_stay_strings() {
+        let user_data = wireguard_cloud_config("[Interface]\n", Distro::Ubuntu)
+            .to_user_data()
+            .unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&user_data).unwrap();
+
+        assert_eq!(value["write_files"][0]["path"], "/etc/wireguard/wg0.conf");
+        assert_eq!(value["write_files"][0]["permissions"], "0600");
+    }
+
+    #[test]
+    fn file_contents_are_kept_verbatim() {
+        let nat_script = "#!/bin/sh\niptables \"$1\" FORWARD -i tun0 -j ACCEPT\n";
+        let config = openvpn_cloud_config("port 1194\n\nverb 3\n", nat_script);
+        let parsed = parse(&config.to_user_data().unwrap());
+
+        assert_eq!(parsed.write_files[0].content, "port 1194\n\nverb 3\n");
+        assert_eq!(parsed.write_files[1].content, nat_script);
+        assert_eq!(parsed.write_files[1].permissions.as_deref(), Some("0755"));
+    }
+
+    #[test]
+    fn tailscale_retry_loop_is_posix() {
+        let config = tailscale_cloud_config("tskey-auth-abc", "tailscale-vpn-1", None);
+        let shell_commands: Vec<&str> = config
+            .runcmd
+            .iter()
+            .filter_map(|command| match command {
+                Command::Shell(command) => Some(command.as_str()),
+                Command::Exec(_) => None,
+            })
+            .collect();
+
+        assert!(shell_commands
+            .iter()
+            .all(|command| !command.contains("{1..")));
+        let retry = shell_commands
+            .iter()
+            .find(|command| command.contains("tailscale up"))
+            .unwrap();
+        assert!(retry.starts_with("for i in $(seq 1 10); do "));
+        assert!(retry.contains("--auth-key=tskey-auth-abc --hostname=tailscale-vpn-1 "));
+        // A spent single-use key is never retried once the node is logged in
+        let logged_in = retry
+            .find("if tailscale status >/dev/null 2>&1; then")
+            .unwrap();
+        assert!(logged_in < retry.find("tailscale up").unwrap());
+    }
+
+    #[test]
+    fn tailscale_joins_login_server() {
+        let config = tailscale_cloud_config(
+            "key",
+            "tailscale-vpn-1",
+            Some("https://headscale.example.com"),
+        );
+        assert!(config.runcmd.iter().any(|command| matches!(
+            command,
+            Command::Shell(command)
+                if command.contains("--login-server=https://headscale.example.com")
+        )));
+    }
+
+    #[test]
+    fn packages_follow_the_distro() {
+        let debian = wireguard_cloud_config("", Distro::Debian);
+        let fedora = wireguard_cloud_config("", Distro::Fedora);
+
+        assert!(debian.packages.contains(&"wireguard".to_string()));
+        assert!(fedora.packages.contains(&"wireguard-tools".to_string()));
+        assert!(debian.package_update && fedora.package_update);
+    }
+
+    #[test]
+    fn commands_serialize_as_strings_or_lists() {
+        let config = CloudConfig {
+            runcmd: vec![
+                Command::shell("echo hi | tee /tmp/hi"),
+                Command::exec(&["systemctl", "enable", "--now", "wg-quick@wg0"]),
+            ],
+            ..Default::default()
+        };
+        let value: serde_yaml::Value =
+            serde_yaml::from_str(&config.to_user_data().unwrap()).unwrap();
+
+        assert_eq!(value["runcmd"][0], "echo hi | tee /tmp/hi");
+        assert_eq!(value["runcmd"][1][3], "wg-quick@wg0");
+    }
+
+    #[test]
+    fn users_keep_the_default_user() {
+        let config = CloudConfig {
+            users: vec![
+                User::Named("default".to_string()),
+                User::Account {
+                    name: "ops".to_string(),
+                    groups: Some("sudo".to_string()),
+                    sudo: None,
+                    shell: Some("/bin/bash".to_string()),
+                    ssh_authorized_keys: vec!["ssh-ed25519 AAAA ops".to_string()],
+                },
+            ],
+            ..Default::default()
+        };
+        let user_data = config.to_user_data().unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&user_data).unwrap();
+
+        assert_eq!(value["users"][0], "default");
+        assert_eq!(value["users"][1]["name"], "ops");
+        assert!(value["users"][1].get("sudo").is_none());
+        assert_eq!(parse(&user_data), config);
+    }
+
+    #[test]
+    fn extra_entries_are_appended() {
+        let extra = CloudConfigExtra::parse(
+            &[
+                "#cloud-config",
+                "packages: [iptables, htop]",
+                "write_files:",
+                "- path: /etc/ssh/sshd_config.d/90-hardening.conf",
+                "  content: PasswordAuthentication no",
+                "runcmd:",
+                "- systemctl reload ssh",
+                "- [touch, /var/log/hardened]",
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        let mut config = openvpn_cloud_config("port 1194\n", "#!/bin/sh\n");
+        let generated = config.clone();
+        config.merge(&extra).unwrap();
+
+        // Packages the setup already installs are not repeated
+        assert_eq!(config.packages, ["openvpn", "iptables", "htop"]);
+        assert_eq!(
+            config.write_files.last().unwrap().path,
+            "/etc/ssh/sshd_config.d/90-hardening.conf"
+        );
+        // Extra commands run after the VPN is set up
+        assert_eq!(
+            config.runcmd[..generated.runcmd.len()],
+            generated.runcmd[..]
+        );
+        assert_eq!(
+            config.runcmd[generated.runcmd.len()..],
+            [
+                Command::shell("systemctl reload ssh"),
+                Command::exec(&["touch", "/var/log/hardened"]),
+            ]
+        );
+        assert_eq!(parse(&config.to_user_data().unwrap()), config);
+    }
+
+    #[test]
+    fn extra_cannot_replace_vpn_files() {
+        let extra = CloudConfigExtra {
+            write_files: vec![WriteFile::new("/etc/wireguard/wg0.conf", "")],
+            ..Default::default()
+        };
+        let mut config = wireguard_cloud_config("[Interface]\n", Distro::Ubuntu);
+        assert!(config.merge(&extra).is_err());
+    }
+
+    #[test]
+    fn extra_rejects_other_modules() {
+        assert!(CloudConfigExtra::parse("users: [default]\n").is_err());
+        assert!(CloudConfigExtra::parse("packages: htop\n").is_err());
+        assert_eq!(
+            CloudConfigExtra::parse("#cloud-config\n\n").unwrap(),
+            CloudConfigExtra::default()
+        );
+    }
+
+    #[test]
+    fn launch_script_installs_packages_and_quotes_commands() {
+        let script = wireguard_cloud_config("[Interface]\n", Distro::Ubuntu).to_launch_script();
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("apt-get update\n  apt-get install -y wireguard iptables\n"));
+        assert!(script.contains("chmod 0600 /etc/wireguard/wg0.conf\n"));
+        assert!(script.contains("\nsystemctl enable --now wg-quick@wg0\n"));
+        assert!(script.ends_with("echo 'Cloud-init complete. WireGuard setup finished.'\n"));
+    }
+
+    #[test]
+    fn launch_script_writes_files_verbatim() {
+        let dir = std::env::temp_dir().join(format!("launch-script-{}", std::process::id()));
+        let path = dir.join("nested/it's.conf");
+        let content = "EOF\n'quoted' $HOME `id`\n\n";
+        let config = CloudConfig {
+            write_files: vec![WriteFile::private(path.to_str().unwrap(), content)],
+            runcmd: vec![Command::exec(&["test", "-f", path.to_str().unwrap()])],
+            ..Default::default()
+        };
+
+        let status = std::process::Command::new("sh")
+            .args(["-e", "-c", &config.to_launch_script()])
+            .status()
+            .unwrap();
+        let written = fs::read_to_string(&path);
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777
+        };
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(status.success());
+        assert_eq!(written.unwrap(), content);
+        #[cfg(unix)]
+        assert_eq!(mode, 0o600);
+    }
+
+    #[test]
+    fn extra_rejects_unknown_write_files_keys() {
+        let contents = [
+            "write_files:",
+            "- path: /etc/motd",
+            "  content: aGVsbG8K",
+            "  encoding: b64",
+        ]
+        .join("\n");
+        assert!(CloudConfigExtra::parse(&contents).is_err());
+    }
+}
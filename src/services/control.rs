@@ -0,0 +1,115 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::models::TailnetNode;
+use crate::services::headscale::{HeadscaleClient, HeadscaleCredentials};
+use crate::services::tailscale::{TailscaleClient, TailscaleCredentials};
+
+/// The coordination server the VPN server joins: tailscale.com or a
+/// self-hosted Headscale.
+///
+/// Deployments only need it for optional steps (checking the server joined,
+/// approving its routes, minting auth keys), so it is reached through this
+/// trait whichever server is in use.
+#[async_trait]
+pub trait ControlServer: Send + Sync {
+    /// "Tailscale" or "Headscale".
+    fn name(&self) -> &'static str;
+
+    async fn validate_credentials(&self) -> Result<()>;
+
+    /// The most recently seen node registered under `hostname`, if any.
+    async fn find_node(&self, hostname: &str) -> Result<Option<TailnetNode>>;
+
+    /// Approves a node that is waiting for device approval. Headscale has no
+    /// such step, so there is nothing to do by default.
+    async fn authorize_node(&self, _node_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// Enables `routes` on the node, replacing the previously enabled set,
+    /// and returns the routes enabled afterwards.
+    async fn set_routes(&self, node_id: &str, routes: &[String]) -> Result<Vec<String>>;
+
+    /// Creates a single-use, pre-authorized auth key for nodes tagged `tags`.
+    async fn create_auth_key(
+        &self,
+        description: &str,
+        tags: &[String],
+        expiry: Duration,
+    ) -> Result<String>;
+
+    /// Removes every node registered under `hostname` and returns how many
+    /// were deleted.
+    async fn delete_nodes_by_hostname(&self, hostname: &str) -> Result<usize>;
+}
+
+/// Which coordination server to use and the API credentials available for it.
+#[derive(Debug, Clone, Default)]
+pub struct ControlServerSettings {
+    /// Headscale server URL passed to `tailscale up --login-server`; `None`
+    /// uses tailscale.com.
+    pub login_server: Option<String>,
+    pub tailscale: Option<TailscaleCredentials>,
+    pub headscale: Option<HeadscaleCredentials>,
+}
+
+impl ControlServerSettings {
+    /// Settings for `login_server` with API credentials from the environment.
+    pub fn from_environment(login_server: Option<String>) -> Self {
+        Self {
+            login_server,
+            tailscale: TailscaleCredentials::from_environment(),
+            headscale: HeadscaleCredentials::from_environment(),
+        }
+    }
+
+    /// "Tailscale" or "Headscale".
+    pub fn name(&self) -> &'static str {
+        if self.login_server.is_some() {
+            "Headscale"
+        } else {
+            "Tailscale"
+        }
+    }
+
+    /// Whether credentials for the selected server's API are available.
+    pub fn has_api_access(&self) -> bool {
+        match self.login_server {
+            Some(_) => self.headscale.is_some(),
+            None => self.tailscale.is_some(),
+        }
+    }
+
+    /// Whether auth keys can be generated instead of asking for one.
+    pub fn can_generate_auth_keys(&self) -> bool {
+        match self.login_server {
+            Some(_) => self
+                .headscale
+                .as_ref()
+                .is_some_and(|credentials| credentials.user.is_some()),
+            None => self
+                .tailscale
+                .as_ref()
+                .is_some_and(|credentials| credentials.can_generate_auth_keys()),
+        }
+    }
+
+    /// Builds the API client for the selected server, if credentials are
+    /// available.
+    pub async fn connect(&self) -> Result<Option<Arc<dyn ControlServer>>> {
+        let control: Arc<dyn ControlServer> = match (&self.login_server, &self.headscale) {
+            (Some(server_url), Some(credentials)) => {
+                Arc::new(HeadscaleClient::new(server_url, credentials))
+            }
+            (Some(_), None) => return Ok(None),
+            (None, _) => match &self.tailscale {
+                Some(credentials) => Arc::new(TailscaleClient::connect(credentials).await?),
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(control))
+    }
+}
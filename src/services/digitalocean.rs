@@ -1,10 +1,13 @@
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use serde_json::json;
+use std::collections::HashMap;
 use std::time::Duration;
 use tokio::time::sleep;
 
 use crate::models::*;
+use crate::services::provider::CloudProvider;
 
 const DO_API_BASE: &str = "https://api.digitalocean.com/v2";
 
@@ -34,28 +37,89 @@ impl DigitalOceanClient {
     pub async fn validate_token(&self) -> Result<()> {
         let response = self
             .client
-            .get(&format!("{}/account", DO_API_BASE))
+            .get(format!("{}/account", DO_API_BASE))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Invalid token or insufficient permissions"));
+        }
+
+        let account_response: AccountResponse = response.json().await?;
+        if account_response.account.status == "locked" {
+            return Err(anyhow!("DigitalOcean account is locked"));
+        }
+
+        Ok(())
+    }
+
+    pub async fn fetch_regions(&self) -> Result<Vec<Region>> {
+        let response = self
+            .client
+            .get(format!("{}/regions?per_page=200", DO_API_BASE))
             .send()
             .await?;
 
         if response.status().is_success() {
-            Ok(())
+            let regions_response: RegionsResponse = response.json().await?;
+            Ok(regions_response.regions)
         } else {
-            Err(anyhow!("Invalid token or insufficient permissions"))
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list regions: {}", error_text))
         }
     }
 
-    pub async fn create_droplet(
-        &self,
-        auth_key: &str,
-        region: Option<crate::models::RegionOption>,
-    ) -> Result<Droplet> {
-        let cloud_init_script = self.generate_cloud_init_script(auth_key);
-        let mut droplet_request = DropletRequest::default();
+    pub async fn fetch_sizes(&self) -> Result<Vec<Size>> {
+        let response = self
+            .client
+            .get(format!("{}/sizes?per_page=200", DO_API_BASE))
+            .send()
+            .await?;
 
-        // Set region if provided
-        if let Some(region_option) = region {
-            droplet_request.region = region_option.slug;
+        if response.status().is_success() {
+            let sizes_response: SizesResponse = response.json().await?;
+            Ok(sizes_response.sizes)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list sizes: {}", error_text))
+        }
+    }
+
+    pub async fn fetch_images(&self) -> Result<Vec<Image>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/images?type=distribution&per_page=200",
+                DO_API_BASE
+            ))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let images_response: ImagesResponse = response.json().await?;
+            Ok(images_response.images)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list images: {}", error_text))
+        }
+    }
+
+    pub async fn create_droplet(&self, request: &ServerRequest) -> Result<Droplet> {
+        let mut droplet_request = DropletRequest {
+            name: request.name.clone(),
+            tags: request.tags.clone(),
+            ..DropletRequest::default()
+        };
+
+        // Set region and size if provided
+        if let Some(region) = &request.region {
+            droplet_request.region = region.clone();
+        }
+        if let Some(size) = &request.size {
+            droplet_request.size = size.clone();
+        }
+        if let Some(image) = &request.image {
+            droplet_request.image = image.clone();
         }
 
         // Add cloud-init script
@@ -65,13 +129,14 @@ impl DigitalOceanClient {
             "size": droplet_request.size,
             "image": droplet_request.image,
             "monitoring": droplet_request.monitoring,
+            "ipv6": droplet_request.ipv6,
             "tags": droplet_request.tags,
-            "user_data": cloud_init_script
+            "user_data": request.user_data
         });
 
         let response = self
             .client
-            .post(&format!("{}/droplets", DO_API_BASE))
+            .post(format!("{}/droplets", DO_API_BASE))
             .json(&payload)
             .send()
             .await?;
@@ -92,7 +157,7 @@ impl DigitalOceanClient {
         loop {
             let response = self
                 .client
-                .get(&format!("{}/droplets/{}", DO_API_BASE, droplet_id))
+                .get(format!("{}/droplets/{}", DO_API_BASE, droplet_id))
                 .send()
                 .await?;
 
@@ -102,17 +167,21 @@ impl DigitalOceanClient {
 
                 if droplet.status == "active" {
                     // Find the public IP
-                    let public_ip = droplet
+                    let public_ip =
+                        public_ipv4(&droplet).ok_or_else(|| anyhow!("No public IP found"))?;
+
+                    let public_ipv6 = droplet
                         .networks
-                        .v4
+                        .v6
                         .iter()
                         .find(|net| net.network_type == "public")
-                        .map(|net| net.ip_address.clone())
-                        .ok_or_else(|| anyhow!("No public IP found"))?;
+                        .map(|net| net.ip_address.clone());
 
                     return Ok(ServerInfo {
                         name: droplet.name,
+                        region: droplet.region.slug,
                         ip: public_ip,
+                        ipv6: public_ipv6,
                     });
                 }
             }
@@ -126,47 +195,162 @@ impl DigitalOceanClient {
         }
     }
 
-    fn generate_cloud_init_script(&self, auth_key: &str) -> String {
-        format!(
-            r#"#cloud-config
-    packages:
-      - curl
-      - wget
+    pub async fn fetch_tagged_droplets(&self, tag: &str) -> Result<Vec<Droplet>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/droplets?tag_name={}&per_page=200",
+                DO_API_BASE, tag
+            ))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let droplets_response: DropletsResponse = response.json().await?;
+            Ok(droplets_response.droplets)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list droplets: {}", error_text))
+        }
+    }
+
+    pub async fn delete_droplet(&self, droplet_id: u64) -> Result<()> {
+        let response = self
+            .client
+            .delete(format!("{}/droplets/{}", DO_API_BASE, droplet_id))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to delete droplet: {}", error_text))
+        }
+    }
+}
 
-    runcmd:
-      # Install Tailscale
-      - ['sh', '-c', 'curl -fsSL https://tailscale.com/install.sh | sh']
+#[async_trait]
+impl CloudProvider for DigitalOceanClient {
+    fn name(&self) -> &'static str {
+        "DigitalOcean"
+    }
+
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::DigitalOcean
+    }
+
+    fn default_size(&self) -> String {
+        DropletRequest::default().size
+    }
 
-      # Configure IP forwarding
-      - ['sh', '-c', 'echo "net.ipv4.ip_forward = 1" | tee -a /etc/sysctl.d/99-tailscale.conf']
-      - ['sh', '-c', 'echo "net.ipv6.conf.all.forwarding = 1" | tee -a /etc/sysctl.d/99-tailscale.conf']
-      - ['sysctl', '-p', '/etc/sysctl.d/99-tailscale.conf']
+    fn default_image(&self) -> String {
+        DropletRequest::default().image
+    }
 
-      # Clean up any existing machine identity
-      # See: https://github.com/tailscale/tailscale/issues/9382
-      - ['systemctl', 'stop', 'tailscaled']
-      - ['sh', '-c', 'rm -rf /var/lib/tailscale/* || true']
+    async fn validate_credentials(&self) -> Result<()> {
+        self.validate_token().await
+    }
 
-      # Start Tailscale daemon with clean state
-      - ['systemctl', 'enable', 'tailscaled']
-      - ['systemctl', 'start', 'tailscaled']
+    async fn list_regions(&self) -> Result<Vec<RegionOption>> {
+        let regions = self.fetch_regions().await?;
+        Ok(regions
+            .into_iter()
+            .filter(|region| region.available)
+            .map(|region| RegionOption {
+                description: region_description(&region.slug),
+                name: region.name,
+                slug: region.slug,
+            })
+            .collect())
+    }
+
+    async fn list_sizes(&self) -> Result<Vec<SizeOption>> {
+        let sizes = self.fetch_sizes().await?;
+        Ok(sizes
+            .into_iter()
+            .filter(|size| size.available)
+            .map(|size| SizeOption {
+                slug: size.slug,
+                memory: size.memory,
+                vcpus: size.vcpus,
+                disk: size.disk,
+                transfer: size.transfer,
+                price_monthly: size.price_monthly,
+                currency: "$",
+                region_prices: HashMap::new(),
+                regions: size.regions,
+            })
+            .collect())
+    }
 
-      # Wait for daemon to be ready
-      - ['sleep', '10']
+    async fn list_images(&self) -> Result<Vec<ImageOption>> {
+        let images = self.fetch_images().await?;
+        Ok(images
+            .into_iter()
+            .filter_map(|image| {
+                ImageOption::supported(image.slug?, &image.distribution, &image.name)
+            })
+            .collect())
+    }
 
-      # Connect to Tailscale with auth key (with retry logic)
-      - ['sh', '-c', 'for i in {{1..10}}; do if tailscale up --reset --force-reauth --auth-key={} --accept-routes --advertise-exit-node; then echo "Tailscale connected successfully on attempt $i"; break; else echo "Attempt $i failed, retrying in 1 second..."; sleep 1; fi; done']
+    async fn create_server(&self, request: &ServerRequest) -> Result<String> {
+        let droplet = self.create_droplet(request).await?;
+        Ok(droplet.id.to_string())
+    }
 
-      # Enable SSH access
-      - ['tailscale', 'set', '--ssh']
+    async fn wait_for_server_ready(&self, server_id: &str) -> Result<ServerInfo> {
+        self.wait_for_droplet_ready(parse_droplet_id(server_id)?)
+            .await
+    }
 
-      # Log success
-      - ['sh', '-c', 'echo "SUCCESS: Tailscale connected at $(date)" > /var/log/tailscale-success.log']
-      - ['sh', '-c', 'tailscale status >> /var/log/tailscale-success.log 2>&1']
+    async fn list_servers(&self) -> Result<ServerList> {
+        let droplets = self.fetch_tagged_droplets(SERVER_TAG).await?;
+        Ok(droplets
+            .into_iter()
+            .map(|droplet| DeployedServer {
+                id: droplet.id.to_string(),
+                ip: public_ipv4(&droplet),
+                name: droplet.name,
+                region: droplet.region.slug,
+                size: droplet.size_slug,
+                status: droplet.status,
+                created_at: droplet.created_at,
+            })
+            .collect())
+    }
 
-    final_message: "Cloud-init complete. Tailscale setup finished."
-    "#,
-            auth_key
-        )
+    async fn destroy_server(&self, server_id: &str) -> Result<()> {
+        self.delete_droplet(parse_droplet_id(server_id)?).await
     }
 }
+
+fn parse_droplet_id(server_id: &str) -> Result<u64> {
+    server_id
+        .parse()
+        .map_err(|_| anyhow!("Invalid droplet id: {}", server_id))
+}
+
+fn public_ipv4(droplet: &Droplet) -> Option<String> {
+    droplet
+        .networks
+        .v4
+        .iter()
+        .find(|net| net.network_type == "public")
+        .map(|net| net.ip_address.clone())
+}
+
+fn region_description(slug: &str) -> String {
+    let country = match slug.get(..3).unwrap_or(slug) {
+        "nyc" | "sfo" | "atl" => "United States",
+        "ams" => "Netherlands",
+        "sgp" => "Singapore",
+        "lon" => "United Kingdom",
+        "fra" => "Germany",
+        "tor" => "Canada",
+        "blr" => "India",
+        "syd" => "Australia",
+        _ => return slug.to_uppercase(),
+    };
+    format!("{} - {}", slug.to_uppercase(), country)
+}
@@ -0,0 +1,221 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use serde_json::json;
+use std::time::Duration;
+
+use crate::models::headscale::*;
+use crate::models::TailnetNode;
+use crate::services::control::ControlServer;
+
+/// API key and user for a self-hosted Headscale server.
+#[derive(Debug, Clone)]
+pub struct HeadscaleCredentials {
+    /// Created with `headscale apikeys create`.
+    pub api_key: String,
+    /// User that generated pre-auth keys belong to.
+    pub user: Option<String>,
+}
+
+impl HeadscaleCredentials {
+    /// `HEADSCALE_API_KEY`, with the user from `HEADSCALE_USER`.
+    pub fn from_environment() -> Option<Self> {
+        let env_var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        env_var("HEADSCALE_API_KEY").map(|api_key| Self {
+            api_key,
+            user: env_var("HEADSCALE_USER"),
+        })
+    }
+}
+
+/// Client for the Headscale REST API.
+#[derive(Clone)]
+pub struct HeadscaleClient {
+    client: reqwest::Client,
+    /// Server URL without a trailing slash, e.g. `https://headscale.example.com`.
+    base_url: String,
+    user: Option<String>,
+}
+
+impl HeadscaleClient {
+    pub fn new(server_url: &str, credentials: &HeadscaleCredentials) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", credentials.api_key)).unwrap(),
+        );
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self {
+            client,
+            base_url: server_url.trim_end_matches('/').to_string(),
+            user: credentials.user.clone(),
+        }
+    }
+
+    pub async fn list_nodes(&self) -> Result<Vec<Node>> {
+        let response = self
+            .client
+            .get(format!("{}/api/v1/node", self.base_url))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let nodes_response: NodesResponse = response.json().await?;
+            Ok(nodes_response.nodes)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list Headscale nodes: {}", error_text))
+        }
+    }
+
+    pub async fn fetch_user_id(&self, name: &str) -> Result<String> {
+        let response = self
+            .client
+            .get(format!("{}/api/v1/user", self.base_url))
+            .query(&[("name", name)])
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let users_response: UsersResponse = response.json().await?;
+            users_response
+                .users
+                .into_iter()
+                .find(|user| user.name == name)
+                .map(|user| user.id)
+                .ok_or_else(|| anyhow!("Headscale user {} not found", name))
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to look up Headscale user: {}", error_text))
+        }
+    }
+
+    pub async fn create_pre_auth_key(&self, request: &CreatePreAuthKeyRequest) -> Result<String> {
+        let response = self
+            .client
+            .post(format!("{}/api/v1/preauthkey", self.base_url))
+            .json(request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let key_response: PreAuthKeyResponse = response.json().await?;
+            Ok(key_response.pre_auth_key.key)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to create pre-auth key: {}", error_text))
+        }
+    }
+
+    pub async fn approve_routes(&self, node_id: &str, routes: &[String]) -> Result<Node> {
+        let response = self
+            .client
+            .post(format!(
+                "{}/api/v1/node/{}/approve_routes",
+                self.base_url, node_id
+            ))
+            .json(&json!({ "routes": routes }))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let node_response: NodeResponse = response.json().await?;
+            Ok(node_response.node)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to approve routes: {}", error_text))
+        }
+    }
+
+    pub async fn delete_node(&self, node_id: &str) -> Result<()> {
+        let response = self
+            .client
+            .delete(format!("{}/api/v1/node/{}", self.base_url, node_id))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to delete Headscale node: {}", error_text))
+        }
+    }
+}
+
+fn to_tailnet_node(node: Node) -> TailnetNode {
+    TailnetNode {
+        id: node.id,
+        name: node.given_name,
+        hostname: node.name,
+        online: node.online,
+        // Headscale nodes are authorized when they register
+        authorized: true,
+        advertised_routes: node.available_routes,
+        enabled_routes: node.approved_routes,
+    }
+}
+
+#[async_trait]
+impl ControlServer for HeadscaleClient {
+    fn name(&self) -> &'static str {
+        "Headscale"
+    }
+
+    async fn validate_credentials(&self) -> Result<()> {
+        self.list_nodes().await.map(|_| ())
+    }
+
+    async fn find_node(&self, hostname: &str) -> Result<Option<TailnetNode>> {
+        let nodes = self.list_nodes().await?;
+        Ok(nodes
+            .into_iter()
+            .filter(|node| node.name == hostname)
+            .max_by_key(|node| node.last_seen)
+            .map(to_tailnet_node))
+    }
+
+    async fn set_routes(&self, node_id: &str, routes: &[String]) -> Result<Vec<String>> {
+        let node = self.approve_routes(node_id, routes).await?;
+        Ok(node.approved_routes)
+    }
+
+    async fn create_auth_key(
+        &self,
+        _description: &str,
+        tags: &[String],
+        expiry: Duration,
+    ) -> Result<String> {
+        let user = self
+            .user
+            .as_deref()
+            .ok_or_else(|| anyhow!("Set HEADSCALE_USER to generate pre-auth keys"))?;
+        let request = CreatePreAuthKeyRequest {
+            user: self.fetch_user_id(user).await?,
+            reusable: false,
+            ephemeral: false,
+            expiration: Utc::now() + expiry,
+            acl_tags: tags.to_vec(),
+        };
+        self.create_pre_auth_key(&request).await
+    }
+
+    async fn delete_nodes_by_hostname(&self, hostname: &str) -> Result<usize> {
+        let nodes = self.list_nodes().await?;
+        let mut deleted = 0;
+        for node in nodes.iter().filter(|node| node.name == hostname) {
+            self.delete_node(&node.id).await?;
+            deleted += 1;
+        }
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,347 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::models::hetzner::*;
+use crate::models::{
+    DeployedServer, ImageOption, ProviderKind, RegionOption, ServerInfo, ServerList, ServerRequest,
+    SizeOption, SERVER_TAG,
+};
+use crate::services::provider::CloudProvider;
+
+const HETZNER_API_BASE: &str = "https://api.hetzner.cloud/v1";
+
+#[derive(Clone)]
+pub struct HetznerClient {
+    client: reqwest::Client,
+}
+
+impl HetznerClient {
+    pub fn new(token: String) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self { client }
+    }
+
+    pub async fn validate_token(&self) -> Result<()> {
+        // Hetzner tokens are scoped to a project, so there is no account
+        // endpoint; listing servers is the cheapest authenticated call.
+        let response = self
+            .client
+            .get(format!("{}/servers?per_page=1", HETZNER_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid token or insufficient permissions"))
+        }
+    }
+
+    pub async fn fetch_locations(&self) -> Result<Vec<Location>> {
+        let response = self
+            .client
+            .get(format!("{}/locations", HETZNER_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let locations_response: LocationsResponse = response.json().await?;
+            Ok(locations_response.locations)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list locations: {}", error_text))
+        }
+    }
+
+    pub async fn fetch_server_types(&self) -> Result<Vec<ServerType>> {
+        let response = self
+            .client
+            .get(format!("{}/server_types?per_page=50", HETZNER_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let server_types_response: ServerTypesResponse = response.json().await?;
+            Ok(server_types_response.server_types)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list server types: {}", error_text))
+        }
+    }
+
+    pub async fn fetch_images(&self) -> Result<Vec<Image>> {
+        // Image names are shared across architectures, so x86 alone covers
+        // every server type
+        let response = self
+            .client
+            .get(format!(
+                "{}/images?type=system&status=available&architecture=x86&per_page=50",
+                HETZNER_API_BASE
+            ))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let images_response: ImagesResponse = response.json().await?;
+            Ok(images_response.images)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list images: {}", error_text))
+        }
+    }
+
+    pub async fn launch_server(&self, request: &ServerRequest) -> Result<Server> {
+        let mut server_request = CreateServerRequest {
+            name: request.name.clone(),
+            user_data: request.user_data.clone(),
+            labels: request
+                .tags
+                .iter()
+                .map(|tag| (tag.clone(), String::new()))
+                .collect(),
+            ..CreateServerRequest::default()
+        };
+
+        if let Some(region) = &request.region {
+            server_request.location = region.clone();
+        }
+        if let Some(size) = &request.size {
+            server_request.server_type = size.clone();
+        }
+        if let Some(image) = &request.image {
+            server_request.image = image.clone();
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/servers", HETZNER_API_BASE))
+            .json(&server_request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let server_response: ServerResponse = response.json().await?;
+            Ok(server_response.server)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to create server: {}", error_text))
+        }
+    }
+
+    pub async fn wait_for_server_running(&self, server_id: u64) -> Result<ServerInfo> {
+        let mut attempts = 0;
+        let max_attempts = 60; // 5 minutes with 5-second intervals
+
+        loop {
+            let response = self
+                .client
+                .get(format!("{}/servers/{}", HETZNER_API_BASE, server_id))
+                .send()
+                .await?;
+
+            if response.status().is_success() {
+                let server_response: ServerResponse = response.json().await?;
+                let server = server_response.server;
+
+                if server.status == "running" {
+                    let public_ip = server
+                        .public_net
+                        .ipv4
+                        .map(|ipv4| ipv4.ip)
+                        .ok_or_else(|| anyhow!("No public IP found"))?;
+
+                    return Ok(ServerInfo {
+                        name: server.name,
+                        region: server.datacenter.location.name,
+                        ip: public_ip,
+                        ipv6: server.public_net.ipv6.map(|ipv6| first_address(&ipv6.ip)),
+                    });
+                }
+            }
+
+            attempts += 1;
+            if attempts >= max_attempts {
+                return Err(anyhow!("Timeout waiting for server to be ready"));
+            }
+
+            sleep(Duration::from_secs(5)).await;
+        }
+    }
+
+    pub async fn fetch_labeled_servers(&self, label: &str) -> Result<Vec<Server>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/servers?label_selector={}&per_page=50",
+                HETZNER_API_BASE, label
+            ))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let servers_response: ServersResponse = response.json().await?;
+            Ok(servers_response.servers)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list servers: {}", error_text))
+        }
+    }
+
+    pub async fn delete_server(&self, server_id: u64) -> Result<()> {
+        let response = self
+            .client
+            .delete(format!("{}/servers/{}", HETZNER_API_BASE, server_id))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to delete server: {}", error_text))
+        }
+    }
+}
+
+#[async_trait]
+impl CloudProvider for HetznerClient {
+    fn name(&self) -> &'static str {
+        "Hetzner Cloud"
+    }
+
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Hetzner
+    }
+
+    fn default_size(&self) -> String {
+        CreateServerRequest::default().server_type
+    }
+
+    fn default_image(&self) -> String {
+        CreateServerRequest::default().image
+    }
+
+    async fn validate_credentials(&self) -> Result<()> {
+        self.validate_token().await
+    }
+
+    async fn list_regions(&self) -> Result<Vec<RegionOption>> {
+        let locations = self.fetch_locations().await?;
+        Ok(locations
+            .into_iter()
+            .map(|location| RegionOption {
+                name: location.city,
+                description: format!("{} - {}", location.name.to_uppercase(), location.country),
+                slug: location.name,
+            })
+            .collect())
+    }
+
+    async fn list_sizes(&self) -> Result<Vec<SizeOption>> {
+        let server_types = self.fetch_server_types().await?;
+        Ok(server_types
+            .into_iter()
+            .filter(|server_type| !server_type.deprecated.unwrap_or(false))
+            .map(|server_type| {
+                let cheapest = server_type
+                    .prices
+                    .iter()
+                    .min_by(|a, b| gross_price(a).total_cmp(&gross_price(b)));
+
+                SizeOption {
+                    slug: server_type.name.clone(),
+                    memory: (server_type.memory * 1024.0) as u32,
+                    vcpus: server_type.cores,
+                    disk: server_type.disk,
+                    transfer: cheapest
+                        .map(|price| price.included_traffic as f64 / 1e12)
+                        .unwrap_or_default(),
+                    price_monthly: cheapest.map(gross_price).unwrap_or_default(),
+                    currency: "€",
+                    region_prices: server_type
+                        .prices
+                        .iter()
+                        .map(|price| (price.location.clone(), gross_price(price)))
+                        .collect(),
+                    regions: server_type
+                        .prices
+                        .iter()
+                        .map(|price| price.location.clone())
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+
+    async fn list_images(&self) -> Result<Vec<ImageOption>> {
+        let images = self.fetch_images().await?;
+        Ok(images
+            .into_iter()
+            .filter(|image| image.deprecated.is_none())
+            .filter_map(|image| {
+                ImageOption::supported(image.name?, &image.os_flavor, &image.os_version?)
+            })
+            .collect())
+    }
+
+    async fn create_server(&self, request: &ServerRequest) -> Result<String> {
+        let server = self.launch_server(request).await?;
+        Ok(server.id.to_string())
+    }
+
+    async fn wait_for_server_ready(&self, server_id: &str) -> Result<ServerInfo> {
+        self.wait_for_server_running(parse_server_id(server_id)?)
+            .await
+    }
+
+    async fn list_servers(&self) -> Result<ServerList> {
+        let servers = self.fetch_labeled_servers(SERVER_TAG).await?;
+        Ok(servers
+            .into_iter()
+            .map(|server| DeployedServer {
+                id: server.id.to_string(),
+                name: server.name,
+                region: server.datacenter.location.name,
+                size: server.server_type.name,
+                status: server.status,
+                ip: server.public_net.ipv4.map(|ipv4| ipv4.ip),
+                created_at: server.created,
+            })
+            .collect())
+    }
+
+    async fn destroy_server(&self, server_id: &str) -> Result<()> {
+        self.delete_server(parse_server_id(server_id)?).await
+    }
+}
+
+fn parse_server_id(server_id: &str) -> Result<u64> {
+    server_id
+        .parse()
+        .map_err(|_| anyhow!("Invalid server id: {}", server_id))
+}
+
+fn gross_price(price: &ServerTypePrice) -> f64 {
+    price.price_monthly.gross.parse().unwrap_or_default()
+}
+
+/// Hetzner assigns a /64 per server; the host itself answers on `::1`.
+fn first_address(network: &str) -> String {
+    let prefix = network.split('/').next().unwrap_or(network);
+    format!("{}1", prefix)
+}
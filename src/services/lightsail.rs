@@ -0,0 +1,426 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio::time::sleep;
+
+use crate::models::lightsail::*;
+use crate::models::{
+    DeployedServer, ImageOption, InboundPort, ProviderKind, RegionOption, ServerInfo, ServerList,
+    ServerRequest, SizeOption, SERVER_TAG,
+};
+use crate::services::cloud_init::CloudConfig;
+use crate::services::provider::CloudProvider;
+use crate::services::sigv4::{sign_request, AwsCredentials};
+
+const LIGHTSAIL_API_VERSION: &str = "Lightsail_20161128";
+const LIGHTSAIL_CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+
+#[derive(Clone)]
+pub struct LightsailClient {
+    client: reqwest::Client,
+    credentials: AwsCredentials,
+    /// Region used for account-wide calls such as listing regions.
+    home_region: String,
+}
+
+impl LightsailClient {
+    pub fn new(credentials: AwsCredentials, home_region: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self {
+            client,
+            credentials,
+            home_region,
+        }
+    }
+
+    /// Builds a client from the standard AWS environment variables or the
+    /// given (or `AWS_PROFILE`/default) profile in `~/.aws`.
+    pub fn from_environment(profile: Option<&str>) -> Result<Self> {
+        let (credentials, region) = AwsCredentials::load(profile)?;
+        Ok(Self::new(credentials, region))
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        region: &str,
+        action: &str,
+        payload: serde_json::Value,
+    ) -> Result<T> {
+        let host = format!("lightsail.{}.amazonaws.com", region);
+        let target = format!("{}.{}", LIGHTSAIL_API_VERSION, action);
+        let body = serde_json::to_vec(&payload)?;
+
+        let headers = sign_request(
+            &self.credentials,
+            region,
+            "lightsail",
+            "POST",
+            &host,
+            "/",
+            &[
+                ("content-type", LIGHTSAIL_CONTENT_TYPE),
+                ("x-amz-target", &target),
+            ],
+            &body,
+            Utc::now(),
+        );
+
+        let mut request = self.client.post(format!("https://{}/", host)).body(body);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+
+        let response = request.send().await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Lightsail {} failed: {}", action, error_text))
+        }
+    }
+
+    pub async fn fetch_regions(&self) -> Result<Vec<Region>> {
+        let response: GetRegionsResponse = self
+            .call(
+                &self.home_region,
+                "GetRegions",
+                json!({ "includeAvailabilityZones": true }),
+            )
+            .await?;
+        Ok(response.regions)
+    }
+
+    /// The first available zone of `region`, since instances are created in
+    /// a zone rather than a region.
+    async fn availability_zone(&self, region: &str) -> Result<String> {
+        self.fetch_regions()
+            .await?
+            .into_iter()
+            .find(|candidate| candidate.name == region)
+            .and_then(|region| {
+                region
+                    .availability_zones
+                    .into_iter()
+                    .find(|zone| zone.state == "available")
+            })
+            .map(|zone| zone.zone_name)
+            .ok_or_else(|| anyhow!("No available zone in Lightsail region {}", region))
+    }
+
+    pub async fn fetch_bundles(&self) -> Result<Vec<Bundle>> {
+        let response: GetBundlesResponse = self
+            .call(
+                &self.home_region,
+                "GetBundles",
+                json!({ "includeInactive": false }),
+            )
+            .await?;
+        Ok(response.bundles)
+    }
+
+    pub async fn fetch_blueprints(&self) -> Result<Vec<Blueprint>> {
+        let response: GetBlueprintsResponse = self
+            .call(
+                &self.home_region,
+                "GetBlueprints",
+                json!({ "includeInactive": false }),
+            )
+            .await?;
+        Ok(response.blueprints)
+    }
+
+    pub async fn create_instance(&self, request: &ServerRequest) -> Result<String> {
+        let region = request
+            .region
+            .clone()
+            .unwrap_or_else(|| self.home_region.clone());
+
+        let mut instance_request = CreateInstancesRequest {
+            instance_names: vec![request.name.clone()],
+            availability_zone: self.availability_zone(&region).await?,
+            user_data: request.user_data.clone(),
+            tags: request
+                .tags
+                .iter()
+                .map(|tag| Tag { key: tag.clone() })
+                .collect(),
+            ..CreateInstancesRequest::default()
+        };
+
+        if let Some(size) = &request.size {
+            instance_request.bundle_id = size.clone();
+        }
+        if let Some(image) = &request.image {
+            instance_request.blueprint_id = image.clone();
+        }
+
+        let _: serde_json::Value = self
+            .call(
+                &region,
+                "CreateInstances",
+                serde_json::to_value(&instance_request)?,
+            )
+            .await?;
+
+        Ok(format_instance_id(&region, &request.name))
+    }
+
+    pub async fn wait_for_instance_ready(
+        &self,
+        region: &str,
+        instance_name: &str,
+    ) -> Result<ServerInfo> {
+        let mut attempts = 0;
+        let max_attempts = 60; // 5 minutes with 5-second intervals
+
+        loop {
+            let response: Result<GetInstanceResponse> = self
+                .call(
+                    region,
+                    "GetInstance",
+                    json!({ "instanceName": instance_name }),
+                )
+                .await;
+
+            if let Ok(response) = response {
+                let instance = response.instance;
+
+                if instance.state.name == "running" {
+                    if let Some(public_ip) = instance.public_ip_address {
+                        return Ok(ServerInfo {
+                            name: instance.name,
+                            region: instance.location.region_name,
+                            ip: public_ip,
+                            ipv6: instance.ipv6_addresses.into_iter().next(),
+                        });
+                    }
+                }
+            }
+
+            attempts += 1;
+            if attempts >= max_attempts {
+                return Err(anyhow!("Timeout waiting for instance to be ready"));
+            }
+
+            sleep(Duration::from_secs(5)).await;
+        }
+    }
+
+    pub async fn open_instance_port(
+        &self,
+        region: &str,
+        instance_name: &str,
+        port: &InboundPort,
+    ) -> Result<()> {
+        let _: serde_json::Value = self
+            .call(
+                region,
+                "OpenInstancePublicPorts",
+                json!({
+                    "instanceName": instance_name,
+                    "portInfo": {
+                        "fromPort": port.port,
+                        "toPort": port.port,
+                        "protocol": port.protocol.as_str(),
+                    },
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn fetch_instances(&self, region: &str) -> Result<Vec<Instance>> {
+        let response: GetInstancesResponse = self.call(region, "GetInstances", json!({})).await?;
+        Ok(response.instances)
+    }
+
+    pub async fn delete_instance(&self, region: &str, instance_name: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .call(
+                region,
+                "DeleteInstance",
+                json!({ "instanceName": instance_name }),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CloudProvider for LightsailClient {
+    fn name(&self) -> &'static str {
+        "AWS Lightsail"
+    }
+
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Lightsail
+    }
+
+    fn default_size(&self) -> String {
+        CreateInstancesRequest::default().bundle_id
+    }
+
+    fn default_image(&self) -> String {
+        CreateInstancesRequest::default().blueprint_id
+    }
+
+    async fn validate_credentials(&self) -> Result<()> {
+        self.fetch_regions()
+            .await
+            .map(|_| ())
+            .map_err(|e| anyhow!("Invalid AWS credentials or insufficient permissions: {}", e))
+    }
+
+    async fn list_regions(&self) -> Result<Vec<RegionOption>> {
+        let regions = self.fetch_regions().await?;
+        Ok(regions
+            .into_iter()
+            .map(|region| RegionOption {
+                name: region.display_name,
+                description: region.name.clone(),
+                slug: region.name,
+            })
+            .collect())
+    }
+
+    async fn list_sizes(&self) -> Result<Vec<SizeOption>> {
+        let bundles = self.fetch_bundles().await?;
+        Ok(bundles
+            .into_iter()
+            // IPv6-only bundles have no public IPv4 to report back
+            .filter(|bundle| {
+                bundle.is_active
+                    && !bundle.bundle_id.contains("ipv6")
+                    && bundle.supported_platforms.iter().any(|p| p == "LINUX_UNIX")
+            })
+            .map(|bundle| SizeOption {
+                slug: bundle.bundle_id,
+                memory: (bundle.ram_size_in_gb * 1024.0) as u32,
+                vcpus: bundle.cpu_count,
+                disk: bundle.disk_size_in_gb,
+                transfer: bundle.transfer_per_month_in_gb as f64 / 1000.0,
+                price_monthly: bundle.price,
+                currency: "$",
+                region_prices: HashMap::new(),
+                regions: vec![],
+            })
+            .collect())
+    }
+
+    async fn list_images(&self) -> Result<Vec<ImageOption>> {
+        let blueprints = self.fetch_blueprints().await?;
+        Ok(blueprints
+            .into_iter()
+            .filter(|blueprint| {
+                blueprint.blueprint_type == "os" && blueprint.platform == "LINUX_UNIX"
+            })
+            .filter_map(|blueprint| {
+                ImageOption::supported(blueprint.blueprint_id, &blueprint.name, &blueprint.version)
+            })
+            .collect())
+    }
+
+    /// Lightsail runs user data as part of its own launch script, so the
+    /// cloud-config is rendered as shell.
+    fn user_data(&self, config: &CloudConfig) -> Result<String> {
+        Ok(config.to_launch_script())
+    }
+
+    async fn create_server(&self, request: &ServerRequest) -> Result<String> {
+        self.create_instance(request).await
+    }
+
+    async fn wait_for_server_ready(&self, server_id: &str) -> Result<ServerInfo> {
+        let (region, instance_name) = parse_instance_id(server_id)?;
+        self.wait_for_instance_ready(region, instance_name).await
+    }
+
+    /// New instances only accept SSH and HTTP.
+    async fn open_ports(&self, server_id: &str, ports: &[InboundPort]) -> Result<()> {
+        let (region, instance_name) = parse_instance_id(server_id)?;
+        for port in ports {
+            self.open_instance_port(region, instance_name, port).await?;
+        }
+        Ok(())
+    }
+
+    async fn list_servers(&self) -> Result<ServerList> {
+        // Instances are regional, so every region has to be asked. A region
+        // that fails only leaves its own servers out.
+        let mut requests = JoinSet::new();
+        for region in self.fetch_regions().await? {
+            let client = self.clone();
+            requests.spawn(async move {
+                let instances = client.fetch_instances(&region.name).await;
+                (region.name, instances)
+            });
+        }
+
+        let region_count = requests.len();
+        let mut list = ServerList::default();
+        let mut last_error = None;
+        while let Some(result) = requests.join_next().await {
+            let (region, instances) =
+                result.map_err(|e| anyhow!("Failed to list instances: {}", e))?;
+            match instances {
+                Ok(instances) => list.servers.extend(
+                    instances
+                        .into_iter()
+                        .filter(|instance| instance.tags.iter().any(|tag| tag.key == SERVER_TAG))
+                        .map(deployed_server),
+                ),
+                Err(e) => {
+                    list.failed_regions.push(region);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if let Some(e) = last_error {
+            if list.failed_regions.len() == region_count {
+                return Err(e);
+            }
+        }
+        list.failed_regions.sort();
+        Ok(list)
+    }
+
+    async fn destroy_server(&self, server_id: &str) -> Result<()> {
+        let (region, instance_name) = parse_instance_id(server_id)?;
+        self.delete_instance(region, instance_name).await
+    }
+}
+
+fn deployed_server(instance: Instance) -> DeployedServer {
+    DeployedServer {
+        id: format_instance_id(&instance.location.region_name, &instance.name),
+        name: instance.name,
+        region: instance.location.region_name,
+        size: instance.bundle_id,
+        status: instance.state.name,
+        ip: instance.public_ip_address,
+        created_at: DateTime::from_timestamp(instance.created_at as i64, 0).unwrap_or_default(),
+    }
+}
+
+/// Lightsail addresses instances by name within a region, so both are
+/// encoded in the server id as `region/name`.
+fn format_instance_id(region: &str, instance_name: &str) -> String {
+    format!("{}/{}", region, instance_name)
+}
+
+fn parse_instance_id(server_id: &str) -> Result<(&str, &str)> {
+    server_id
+        .split_once('/')
+        .ok_or_else(|| anyhow!("Invalid instance id: {}", server_id))
+}
@@ -0,0 +1,361 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::models::linode::*;
+use crate::models::{
+    DeployedServer, ImageOption, ProviderKind, RegionOption, ServerInfo, ServerList, ServerRequest,
+    SizeOption, SERVER_TAG,
+};
+use crate::services::provider::CloudProvider;
+
+const LINODE_API_BASE: &str = "https://api.linode.com/v4";
+
+#[derive(Clone)]
+pub struct LinodeClient {
+    client: reqwest::Client,
+}
+
+impl LinodeClient {
+    pub fn new(token: String) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self { client }
+    }
+
+    pub async fn validate_token(&self) -> Result<()> {
+        let response = self
+            .client
+            .get(format!("{}/profile", LINODE_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid token or insufficient permissions"))
+        }
+    }
+
+    pub async fn fetch_regions(&self) -> Result<Vec<Region>> {
+        let response = self
+            .client
+            .get(format!("{}/regions?page_size=500", LINODE_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let page: Page<Region> = response.json().await?;
+            Ok(page.data)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list regions: {}", error_text))
+        }
+    }
+
+    pub async fn fetch_types(&self) -> Result<Vec<LinodeType>> {
+        let response = self
+            .client
+            .get(format!("{}/linode/types?page_size=500", LINODE_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let page: Page<LinodeType> = response.json().await?;
+            Ok(page.data)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list instance types: {}", error_text))
+        }
+    }
+
+    pub async fn fetch_images(&self) -> Result<Vec<Image>> {
+        let response = self
+            .client
+            .get(format!("{}/images?page_size=500", LINODE_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let page: Page<Image> = response.json().await?;
+            Ok(page.data)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list images: {}", error_text))
+        }
+    }
+
+    pub async fn create_instance(&self, request: &ServerRequest) -> Result<Instance> {
+        let mut instance_request = CreateInstanceRequest {
+            label: request.name.clone(),
+            tags: request.tags.clone(),
+            // Linode insists on a root password. It is random and never shown,
+            // so password login as root is effectively disabled.
+            root_pass: format!(
+                "{}{}",
+                uuid::Uuid::new_v4().simple(),
+                uuid::Uuid::new_v4().simple()
+            ),
+            metadata: Metadata {
+                user_data: STANDARD.encode(&request.user_data),
+            },
+            ..CreateInstanceRequest::default()
+        };
+
+        if let Some(region) = &request.region {
+            instance_request.region = region.clone();
+        }
+        if let Some(size) = &request.size {
+            instance_request.instance_type = size.clone();
+        }
+        if let Some(image) = &request.image {
+            instance_request.image = image.clone();
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/linode/instances", LINODE_API_BASE))
+            .json(&instance_request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to create instance: {}", error_text))
+        }
+    }
+
+    pub async fn wait_for_instance_ready(&self, instance_id: u64) -> Result<ServerInfo> {
+        let mut attempts = 0;
+        let max_attempts = 60; // 5 minutes with 5-second intervals
+
+        loop {
+            let response = self
+                .client
+                .get(format!(
+                    "{}/linode/instances/{}",
+                    LINODE_API_BASE, instance_id
+                ))
+                .send()
+                .await?;
+
+            if response.status().is_success() {
+                let instance: Instance = response.json().await?;
+
+                if instance.status == "running" {
+                    let public_ip =
+                        public_ipv4(&instance).ok_or_else(|| anyhow!("No public IP found"))?;
+
+                    let ipv6 = instance
+                        .ipv6
+                        .as_deref()
+                        .and_then(|ip| ip.split('/').next())
+                        .map(|ip| ip.to_string());
+
+                    return Ok(ServerInfo {
+                        name: instance.label,
+                        region: instance.region,
+                        ip: public_ip,
+                        ipv6,
+                    });
+                }
+            }
+
+            attempts += 1;
+            if attempts >= max_attempts {
+                return Err(anyhow!("Timeout waiting for instance to be ready"));
+            }
+
+            sleep(Duration::from_secs(5)).await;
+        }
+    }
+
+    pub async fn fetch_tagged_instances(&self, tag: &str) -> Result<Vec<Instance>> {
+        // Linode filters collections through a JSON header rather than query params
+        let response = self
+            .client
+            .get(format!(
+                "{}/linode/instances?page_size=500",
+                LINODE_API_BASE
+            ))
+            .header("X-Filter", json!({ "tags": tag }).to_string())
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let page: Page<Instance> = response.json().await?;
+            Ok(page.data)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list instances: {}", error_text))
+        }
+    }
+
+    pub async fn delete_instance(&self, instance_id: u64) -> Result<()> {
+        let response = self
+            .client
+            .delete(format!(
+                "{}/linode/instances/{}",
+                LINODE_API_BASE, instance_id
+            ))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to delete instance: {}", error_text))
+        }
+    }
+}
+
+#[async_trait]
+impl CloudProvider for LinodeClient {
+    fn name(&self) -> &'static str {
+        "Linode"
+    }
+
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Linode
+    }
+
+    fn default_size(&self) -> String {
+        CreateInstanceRequest::default().instance_type
+    }
+
+    fn default_image(&self) -> String {
+        CreateInstanceRequest::default().image
+    }
+
+    async fn validate_credentials(&self) -> Result<()> {
+        self.validate_token().await
+    }
+
+    async fn list_regions(&self) -> Result<Vec<RegionOption>> {
+        let regions = self.fetch_regions().await?;
+        Ok(regions
+            .into_iter()
+            // User data is only delivered in regions with the metadata service
+            .filter(|region| {
+                region.status == "ok" && region.capabilities.iter().any(|c| c == "Metadata")
+            })
+            .map(|region| RegionOption {
+                name: region.label,
+                description: format!(
+                    "{} - {}",
+                    region.id.to_uppercase(),
+                    region.country.to_uppercase()
+                ),
+                slug: region.id,
+            })
+            .collect())
+    }
+
+    async fn list_sizes(&self) -> Result<Vec<SizeOption>> {
+        let types = self.fetch_types().await?;
+        Ok(types
+            .into_iter()
+            .map(|linode_type| SizeOption {
+                slug: linode_type.id,
+                memory: linode_type.memory,
+                vcpus: linode_type.vcpus,
+                disk: linode_type.disk / 1024,
+                transfer: linode_type.transfer as f64 / 1000.0,
+                price_monthly: linode_type.price.monthly,
+                currency: "$",
+                region_prices: linode_type
+                    .region_prices
+                    .into_iter()
+                    .map(|price| (price.id, price.monthly))
+                    .collect(),
+                regions: vec![],
+            })
+            .collect())
+    }
+
+    async fn list_images(&self) -> Result<Vec<ImageOption>> {
+        let images = self.fetch_images().await?;
+        Ok(images
+            .into_iter()
+            // Metadata user data is only read by cloud-init enabled images
+            .filter(|image| {
+                image.is_public
+                    && !image.deprecated
+                    && image.capabilities.iter().any(|c| c == "cloud-init")
+            })
+            .filter_map(|image| {
+                ImageOption::supported(image.id, image.vendor.as_deref()?, &image.label)
+            })
+            .collect())
+    }
+
+    async fn create_server(&self, request: &ServerRequest) -> Result<String> {
+        let instance = self.create_instance(request).await?;
+        Ok(instance.id.to_string())
+    }
+
+    async fn wait_for_server_ready(&self, server_id: &str) -> Result<ServerInfo> {
+        self.wait_for_instance_ready(parse_instance_id(server_id)?)
+            .await
+    }
+
+    async fn list_servers(&self) -> Result<ServerList> {
+        let instances = self.fetch_tagged_instances(SERVER_TAG).await?;
+        Ok(instances
+            .into_iter()
+            .map(|instance| DeployedServer {
+                id: instance.id.to_string(),
+                ip: public_ipv4(&instance),
+                name: instance.label,
+                region: instance.region,
+                size: instance.instance_type,
+                status: instance.status,
+                created_at: instance.created.and_utc(),
+            })
+            .collect())
+    }
+
+    async fn destroy_server(&self, server_id: &str) -> Result<()> {
+        self.delete_instance(parse_instance_id(server_id)?).await
+    }
+}
+
+fn parse_instance_id(server_id: &str) -> Result<u64> {
+    server_id
+        .parse()
+        .map_err(|_| anyhow!("Invalid instance id: {}", server_id))
+}
+
+fn public_ipv4(instance: &Instance) -> Option<String> {
+    instance
+        .ipv4
+        .iter()
+        .find(|ip| !is_private_ipv4(ip))
+        .cloned()
+}
+
+/// Linode lists private addresses alongside the public one when private
+/// networking is enabled.
+fn is_private_ipv4(ip: &str) -> bool {
+    ip.parse::<std::net::Ipv4Addr>()
+        .map(|addr| addr.is_private())
+        .unwrap_or(false)
+}
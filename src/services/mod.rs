@@ -1 +1,43 @@
+use anyhow::Result;
+use std::sync::Arc;
+
+use crate::models::ProviderKind;
+use crate::services::digitalocean::DigitalOceanClient;
+use crate::services::hetzner::HetznerClient;
+use crate::services::lightsail::LightsailClient;
+use crate::services::linode::LinodeClient;
+use crate::services::provider::CloudProvider;
+use crate::services::vultr::VultrClient;
+
+pub mod cloud_init;
+pub mod control;
 pub mod digitalocean;
+pub mod headscale;
+pub mod hetzner;
+pub mod lightsail;
+pub mod linode;
+pub mod openvpn;
+pub mod provider;
+pub mod shadowsocks;
+pub mod sigv4;
+pub mod state;
+pub mod tailscale;
+pub mod vpn;
+pub mod vultr;
+pub mod wireguard;
+
+/// Builds the client for `kind` from the value entered on the auth screen:
+/// an API token, or for Lightsail an optional AWS profile name.
+pub fn create_provider(kind: ProviderKind, credential: String) -> Result<Arc<dyn CloudProvider>> {
+    let provider: Arc<dyn CloudProvider> = match kind {
+        ProviderKind::DigitalOcean => Arc::new(DigitalOceanClient::new(credential)),
+        ProviderKind::Hetzner => Arc::new(HetznerClient::new(credential)),
+        ProviderKind::Vultr => Arc::new(VultrClient::new(credential)),
+        ProviderKind::Linode => Arc::new(LinodeClient::new(credential)),
+        ProviderKind::Lightsail => {
+            let profile = Some(credential.as_str()).filter(|p| !p.is_empty());
+            Arc::new(LightsailClient::from_environment(profile)?)
+        }
+    };
+    Ok(provider)
+}
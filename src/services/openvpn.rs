@@ -0,0 +1,289 @@
+//! OpenVPN servers, for machines that only allow OpenVPN clients.
+//!
+//! The tool acts as the certificate authority: the CA, server and client
+//! certificates are generated locally, the server's are embedded in its
+//! config and each client's in its `.ovpn` profile. The CA key is never
+//! stored, so no further certificates can be issued for a server.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{Datelike, Duration as ChronoDuration, Utc};
+use rcgen::{
+    date_time_ymd, BasicConstraints, Certificate, CertificateParams, DnType,
+    ExtendedKeyUsagePurpose, IsCa, KeyPair, KeyUsagePurpose,
+};
+
+use crate::models::{ClientProfile, Distro, InboundPort, Protocol, VpnConnection, VpnKind};
+use crate::services::cloud_init::{nat_rules, openvpn_cloud_config, CloudConfig};
+use crate::services::state::{client_config_dir, write_private_file};
+use crate::services::vpn::{Progress, Readiness, VpnBackend, UNVERIFIED_SETUP_WAIT};
+
+pub const OPENVPN_PORT: InboundPort = InboundPort {
+    port: 1194,
+    protocol: Protocol::Udp,
+};
+
+/// Clients get addresses from a /24, like WireGuard's.
+pub const MAX_OPENVPN_CLIENTS: usize = 253;
+
+const TUNNEL_DEVICE: &str = "tun0";
+const TUNNEL_SUBNET_V4: &str = "10.8.0.0";
+const TUNNEL_SUBNET_V6: &str = "fd42:8:8::/64";
+const CLIENT_DNS: [&str; 2] = ["1.1.1.1", "1.0.0.1"];
+const CERTIFICATE_VALIDITY_YEARS: i32 = 10;
+
+/// A PEM certificate and its private key.
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub certificate: String,
+    pub private_key: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenVpnClient {
+    pub name: String,
+    pub credentials: Credentials,
+}
+
+/// Certificates and keys for a server and its clients.
+#[derive(Debug, Clone)]
+pub struct OpenVpnPki {
+    pub ca_certificate: String,
+    pub server: Credentials,
+    pub clients: Vec<OpenVpnClient>,
+    /// Shared key encrypting the TLS handshake, in OpenVPN's static key format.
+    pub tls_crypt_key: String,
+}
+
+impl OpenVpnPki {
+    pub fn generate(client_count: usize) -> Result<Self> {
+        if client_count == 0 || client_count > MAX_OPENVPN_CLIENTS {
+            return Err(anyhow!(
+                "OpenVPN supports 1 to {} clients",
+                MAX_OPENVPN_CLIENTS
+            ));
+        }
+
+        let ca_key = KeyPair::generate()?;
+        let mut ca_params = certificate_params("vpn-deployer CA")?;
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let ca = ca_params.self_signed(&ca_key)?;
+
+        let server = issue(&ca, &ca_key, "server", ExtendedKeyUsagePurpose::ServerAuth)?;
+        let clients = (1..=client_count)
+            .map(|i| {
+                let name = format!("client-{}", i);
+                let credentials = issue(&ca, &ca_key, &name, ExtendedKeyUsagePurpose::ClientAuth)?;
+                Ok(OpenVpnClient { name, credentials })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            ca_certificate: ca.pem(),
+            server,
+            clients,
+            tls_crypt_key: generate_static_key()?,
+        })
+    }
+
+    /// `/etc/openvpn/server/server.conf`, with the certificates inline.
+    pub fn server_config(&self) -> String {
+        let mut config = format!(
+            "port {port}\n\
+             proto udp\n\
+             dev {device}\n\
+             topology subnet\n\
+             server {v4} 255.255.255.0\n\
+             server-ipv6 {v6}\n\
+             push \"redirect-gateway def1 ipv6 bypass-dhcp\"\n",
+            port = OPENVPN_PORT.port,
+            device = TUNNEL_DEVICE,
+            v4 = TUNNEL_SUBNET_V4,
+            v6 = TUNNEL_SUBNET_V6,
+        );
+        for dns in CLIENT_DNS {
+            config.push_str(&format!("push \"dhcp-option DNS {}\"\n", dns));
+        }
+        config.push_str(
+            "keepalive 10 60\n\
+             persist-key\n\
+             persist-tun\n\
+             dh none\n\
+             tls-version-min 1.2\n\
+             data-ciphers AES-256-GCM:AES-128-GCM:CHACHA20-POLY1305\n\
+             remote-cert-tls client\n\
+             verb 3\n",
+        );
+        config.push_str(&inline_blocks(
+            &self.ca_certificate,
+            &self.server,
+            &self.tls_crypt_key,
+        ));
+        config
+    }
+
+    /// Script run at boot with `-A` (and `-D` on shutdown) to route clients
+    /// to the internet.
+    pub fn nat_script(&self) -> String {
+        format!(
+            "#!/bin/sh\n{}\n",
+            nat_rules(
+                "\"$1\"",
+                TUNNEL_DEVICE,
+                &format!("{}/24", TUNNEL_SUBNET_V4),
+                TUNNEL_SUBNET_V6,
+            )
+        )
+    }
+
+    /// A profile connecting to the server at `endpoint` and sending all of
+    /// the client's traffic through it.
+    pub fn client_profile(&self, client: &OpenVpnClient, endpoint: &str) -> String {
+        let mut profile = format!(
+            "client\n\
+             dev tun\n\
+             proto udp\n\
+             remote {endpoint} {port}\n\
+             resolv-retry infinite\n\
+             nobind\n\
+             persist-key\n\
+             persist-tun\n\
+             remote-cert-tls server\n\
+             verb 3\n",
+            endpoint = endpoint,
+            port = OPENVPN_PORT.port,
+        );
+        profile.push_str(&inline_blocks(
+            &self.ca_certificate,
+            &client.credentials,
+            &self.tls_crypt_key,
+        ));
+        profile
+    }
+
+    /// Writes an `.ovpn` profile per client for the server `server_name`,
+    /// reachable at `endpoint`.
+    pub fn write_client_profiles(
+        &self,
+        server_name: &str,
+        endpoint: &str,
+    ) -> Result<Vec<ClientProfile>> {
+        let dir = client_config_dir(server_name)?;
+        self.clients
+            .iter()
+            .map(|client| {
+                let config = self.client_profile(client, endpoint);
+                let path = dir.join(format!("{}.ovpn", client.name));
+                write_private_file(&path, &config)?;
+                Ok(ClientProfile {
+                    name: client.name.clone(),
+                    path,
+                    config,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl VpnBackend for OpenVpnPki {
+    fn kind(&self) -> VpnKind {
+        VpnKind::OpenVpn
+    }
+
+    fn cloud_config(&self, _server_name: &str, _distro: Distro) -> CloudConfig {
+        openvpn_cloud_config(&self.server_config(), &self.nat_script())
+    }
+
+    fn inbound_ports(&self) -> Vec<InboundPort> {
+        vec![OPENVPN_PORT]
+    }
+
+    fn readiness(&self) -> Readiness {
+        Readiness::FixedWait(UNVERIFIED_SETUP_WAIT)
+    }
+
+    async fn connection(
+        &self,
+        server_name: &str,
+        endpoint: &str,
+        progress: Progress<'_>,
+    ) -> Result<VpnConnection> {
+        progress("Writing client profiles...".to_string());
+        let clients = self
+            .write_client_profiles(server_name, endpoint)
+            .map_err(|e| anyhow!("Failed to write client profiles: {}", e))?;
+        Ok(VpnConnection::OpenVpn { clients })
+    }
+}
+
+/// Parameters for a certificate named `common_name`, valid from yesterday
+/// (to allow for clock skew) for `CERTIFICATE_VALIDITY_YEARS`.
+fn certificate_params(common_name: &str) -> Result<CertificateParams> {
+    let mut params = CertificateParams::new(Vec::<String>::new())?;
+    params
+        .distinguished_name
+        .push(DnType::CommonName, common_name);
+
+    let yesterday = Utc::now() - ChronoDuration::days(1);
+    let (month, day) = (yesterday.month() as u8, yesterday.day() as u8);
+    params.not_before = date_time_ymd(yesterday.year(), month, day);
+    params.not_after = date_time_ymd(
+        yesterday.year() + CERTIFICATE_VALIDITY_YEARS,
+        month,
+        day.min(28),
+    );
+
+    Ok(params)
+}
+
+/// A new key and a certificate for it signed by the CA.
+fn issue(
+    ca: &Certificate,
+    ca_key: &KeyPair,
+    common_name: &str,
+    usage: ExtendedKeyUsagePurpose,
+) -> Result<Credentials> {
+    let key = KeyPair::generate()?;
+    let mut params = certificate_params(common_name)?;
+    params.key_usages = vec![
+        KeyUsagePurpose::DigitalSignature,
+        KeyUsagePurpose::KeyAgreement,
+    ];
+    params.extended_key_usages = vec![usage];
+    let certificate = params.signed_by(&key, ca, ca_key)?;
+
+    Ok(Credentials {
+        certificate: certificate.pem(),
+        private_key: key.serialize_pem(),
+    })
+}
+
+/// 2048 random bits in the format `openvpn --genkey` writes.
+fn generate_static_key() -> Result<String> {
+    let mut key = [0u8; 256];
+    getrandom::getrandom(&mut key)
+        .map_err(|e| anyhow!("Failed to generate the tls-crypt key: {}", e))?;
+
+    let mut static_key = String::from("-----BEGIN OpenVPN Static key V1-----\n");
+    for line in key.chunks(16) {
+        static_key.push_str(&hex::encode(line));
+        static_key.push('\n');
+    }
+    static_key.push_str("-----END OpenVPN Static key V1-----\n");
+    Ok(static_key)
+}
+
+/// The `<ca>`, `<cert>`, `<key>` and `<tls-crypt>` blocks of a config.
+fn inline_blocks(ca_certificate: &str, credentials: &Credentials, tls_crypt_key: &str) -> String {
+    [
+        ("ca", ca_certificate),
+        ("cert", credentials.certificate.as_str()),
+        ("key", credentials.private_key.as_str()),
+        ("tls-crypt", tls_crypt_key),
+    ]
+    .iter()
+    .map(|(tag, contents)| format!("<{}>\n{}\n</{}>\n", tag, contents.trim_end(), tag))
+    .collect()
+}
@@ -0,0 +1,58 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::models::{
+    ImageOption, InboundPort, ProviderKind, RegionOption, ServerInfo, ServerList, ServerRequest,
+    SizeOption,
+};
+use crate::services::cloud_init::CloudConfig;
+
+/// A cloud platform that can host the VPN server.
+///
+/// The deployment pipeline only talks to providers through this trait, so a new
+/// cloud can be added without touching the TUI state machine.
+#[async_trait]
+pub trait CloudProvider: Send + Sync {
+    /// Human readable name shown in the UI, e.g. "DigitalOcean".
+    fn name(&self) -> &'static str;
+
+    fn kind(&self) -> ProviderKind;
+
+    /// Size slug used when the user has not picked one.
+    fn default_size(&self) -> String;
+
+    /// Image used when the user has not picked one; always Ubuntu LTS.
+    fn default_image(&self) -> String;
+
+    async fn validate_credentials(&self) -> Result<()>;
+
+    async fn list_regions(&self) -> Result<Vec<RegionOption>>;
+
+    async fn list_sizes(&self) -> Result<Vec<SizeOption>>;
+
+    /// Distribution images the setup script supports.
+    async fn list_images(&self) -> Result<Vec<ImageOption>>;
+
+    /// `config` in the form the provider passes on to the server at boot.
+    fn user_data(&self, config: &CloudConfig) -> Result<String> {
+        config.to_user_data()
+    }
+
+    /// Creates the server and returns its provider-specific id.
+    async fn create_server(&self, request: &ServerRequest) -> Result<String>;
+
+    /// Polls until the server is running and has a public IP.
+    async fn wait_for_server_ready(&self, server_id: &str) -> Result<ServerInfo>;
+
+    /// Opens `ports` to the internet on a running server. Only needed where
+    /// the provider firewalls new servers; the others accept all traffic.
+    async fn open_ports(&self, _server_id: &str, _ports: &[InboundPort]) -> Result<()> {
+        Ok(())
+    }
+
+    /// Servers tagged with `SERVER_TAG`, i.e. the ones this tool created.
+    async fn list_servers(&self) -> Result<ServerList>;
+
+    /// Deletes the server. Its tailnet device is left to the caller.
+    async fn destroy_server(&self, server_id: &str) -> Result<()>;
+}
@@ -0,0 +1,166 @@
+//! Shadowsocks servers, for networks that block recognisable VPN traffic.
+//!
+//! Each client gets its own port and password, generated locally. Clients
+//! connect with an `ss://` access URL, which Outline and the Shadowsocks
+//! apps import directly.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde_json::json;
+
+use crate::models::{ClientProfile, Distro, InboundPort, Protocol, VpnConnection, VpnKind};
+use crate::services::cloud_init::{shadowsocks_cloud_config, CloudConfig};
+use crate::services::state::{client_config_dir, write_private_file};
+use crate::services::vpn::{Progress, Readiness, VpnBackend, UNVERIFIED_SETUP_WAIT};
+
+/// Port of the first client; the others follow it.
+const FIRST_PORT: u16 = 8388;
+
+/// Every client listens on its own port, so keep the firewall short.
+pub const MAX_SHADOWSOCKS_CLIENTS: usize = 16;
+
+/// Supported by Outline as well as the Shadowsocks clients.
+const METHOD: &str = "chacha20-ietf-poly1305";
+
+#[derive(Debug, Clone)]
+pub struct ShadowsocksClient {
+    pub name: String,
+    pub port: u16,
+    pub password: String,
+}
+
+/// Ports and passwords for a server's clients.
+#[derive(Debug, Clone)]
+pub struct ShadowsocksSetup {
+    pub clients: Vec<ShadowsocksClient>,
+}
+
+impl ShadowsocksSetup {
+    pub fn generate(client_count: usize) -> Result<Self> {
+        if client_count == 0 || client_count > MAX_SHADOWSOCKS_CLIENTS {
+            return Err(anyhow!(
+                "Shadowsocks supports 1 to {} clients",
+                MAX_SHADOWSOCKS_CLIENTS
+            ));
+        }
+
+        let clients = (1..=client_count)
+            .map(|i| {
+                Ok(ShadowsocksClient {
+                    name: format!("client-{}", i),
+                    port: FIRST_PORT + i as u16 - 1,
+                    password: generate_password()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { clients })
+    }
+
+    /// The `ssserver` config, listening on IPv4 and IPv6.
+    pub fn server_config(&self) -> String {
+        let servers: Vec<_> = self
+            .clients
+            .iter()
+            .map(|client| {
+                json!({
+                    "server": "::",
+                    "server_port": client.port,
+                    "password": client.password,
+                    "method": METHOD,
+                })
+            })
+            .collect();
+
+        let config = json!({ "servers": servers, "mode": "tcp_and_udp" });
+        serde_json::to_string_pretty(&config).unwrap_or_default()
+    }
+
+    /// A SIP002 access URL for `client` on the server at `endpoint`, named
+    /// after the server in client apps.
+    pub fn access_url(
+        &self,
+        client: &ShadowsocksClient,
+        endpoint: &str,
+        server_name: &str,
+    ) -> String {
+        let user_info = URL_SAFE_NO_PAD.encode(format!("{}:{}", METHOD, client.password));
+        format!(
+            "ss://{}@{}:{}#{}-{}",
+            user_info, endpoint, client.port, server_name, client.name
+        )
+    }
+
+    /// Writes each client's access URL for the server `server_name`,
+    /// reachable at `endpoint`.
+    pub fn write_access_urls(
+        &self,
+        server_name: &str,
+        endpoint: &str,
+    ) -> Result<Vec<ClientProfile>> {
+        let dir = client_config_dir(server_name)?;
+        self.clients
+            .iter()
+            .map(|client| {
+                let config = self.access_url(client, endpoint, server_name);
+                let path = dir.join(format!("{}.txt", client.name));
+                write_private_file(&path, &format!("{}\n", config))?;
+                Ok(ClientProfile {
+                    name: client.name.clone(),
+                    path,
+                    config,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl VpnBackend for ShadowsocksSetup {
+    fn kind(&self) -> VpnKind {
+        VpnKind::Shadowsocks
+    }
+
+    fn cloud_config(&self, _server_name: &str, distro: Distro) -> CloudConfig {
+        shadowsocks_cloud_config(&self.server_config(), distro)
+    }
+
+    /// Shadowsocks relays UDP on the same port as TCP.
+    fn inbound_ports(&self) -> Vec<InboundPort> {
+        self.clients
+            .iter()
+            .flat_map(|client| {
+                [Protocol::Tcp, Protocol::Udp].map(|protocol| InboundPort {
+                    port: client.port,
+                    protocol,
+                })
+            })
+            .collect()
+    }
+
+    fn readiness(&self) -> Readiness {
+        Readiness::FixedWait(UNVERIFIED_SETUP_WAIT)
+    }
+
+    async fn connection(
+        &self,
+        server_name: &str,
+        endpoint: &str,
+        progress: Progress<'_>,
+    ) -> Result<VpnConnection> {
+        progress("Writing access URLs...".to_string());
+        let clients = self
+            .write_access_urls(server_name, endpoint)
+            .map_err(|e| anyhow!("Failed to write access URLs: {}", e))?;
+        Ok(VpnConnection::Shadowsocks { clients })
+    }
+}
+
+/// 256 random bits, encoded so the password needs no escaping anywhere.
+fn generate_password() -> Result<String> {
+    let mut password = [0u8; 32];
+    getrandom::getrandom(&mut password)
+        .map_err(|e| anyhow!("Failed to generate a Shadowsocks password: {}", e))?;
+    Ok(URL_SAFE_NO_PAD.encode(password))
+}
@@ -0,0 +1,279 @@
+//! Minimal AWS Signature Version 4 signing and credential discovery.
+//!
+//! Only what the Lightsail JSON API needs: signed `POST /` requests with a
+//! handful of headers, and credentials from the standard environment
+//! variables or the shared `~/.aws/credentials` / `~/.aws/config` files.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Clone)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Resolves credentials and the default region the same way the AWS CLI
+    /// does for static credentials: environment variables first, then the
+    /// selected profile in the shared credentials and config files.
+    pub fn load(profile: Option<&str>) -> Result<(Self, String)> {
+        let profile = profile
+            .map(|p| p.to_string())
+            .or_else(|| env_var("AWS_PROFILE"))
+            .unwrap_or_else(|| "default".to_string());
+
+        let credentials_file = env_var("AWS_SHARED_CREDENTIALS_FILE")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".aws").join("credentials")));
+        let config_file = env_var("AWS_CONFIG_FILE")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".aws").join("config")));
+
+        let credentials_section = credentials_file
+            .and_then(|path| read_ini_section(&path, &profile))
+            .unwrap_or_default();
+        // The config file prefixes every profile except the default one
+        let config_section_name = if profile == "default" {
+            profile.clone()
+        } else {
+            format!("profile {}", profile)
+        };
+        let config_section = config_file
+            .and_then(|path| read_ini_section(&path, &config_section_name))
+            .unwrap_or_default();
+
+        let lookup = |env: &str, key: &str| {
+            env_var(env)
+                .or_else(|| credentials_section.get(key).cloned())
+                .or_else(|| config_section.get(key).cloned())
+        };
+
+        let access_key_id = lookup("AWS_ACCESS_KEY_ID", "aws_access_key_id");
+        let secret_access_key = lookup("AWS_SECRET_ACCESS_KEY", "aws_secret_access_key");
+        let session_token = lookup("AWS_SESSION_TOKEN", "aws_session_token");
+
+        let (Some(access_key_id), Some(secret_access_key)) = (access_key_id, secret_access_key)
+        else {
+            return Err(anyhow!(
+                "No AWS credentials found. Set AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY \
+                 or configure the '{}' profile in ~/.aws/credentials",
+                profile
+            ));
+        };
+
+        let region = env_var("AWS_REGION")
+            .or_else(|| env_var("AWS_DEFAULT_REGION"))
+            .or_else(|| config_section.get("region").cloned())
+            .unwrap_or_else(|| "us-east-1".to_string());
+
+        Ok((
+            Self {
+                access_key_id,
+                secret_access_key,
+                session_token,
+            },
+            region,
+        ))
+    }
+}
+
+/// Returns the headers (including `authorization`) to send with a request.
+///
+/// `headers` must already contain every header that should be signed except
+/// `host`, `x-amz-date` and `x-amz-security-token`, which are added here.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_request(
+    credentials: &AwsCredentials,
+    region: &str,
+    service: &str,
+    method: &str,
+    host: &str,
+    path: &str,
+    headers: &[(&str, &str)],
+    body: &[u8],
+    now: DateTime<Utc>,
+) -> Vec<(String, String)> {
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+
+    let mut signed: Vec<(String, String)> = headers
+        .iter()
+        .map(|(name, value)| (name.to_lowercase(), value.trim().to_string()))
+        .collect();
+    signed.push(("host".to_string(), host.to_string()));
+    signed.push(("x-amz-date".to_string(), amz_date.clone()));
+    if let Some(token) = &credentials.session_token {
+        signed.push(("x-amz-security-token".to_string(), token.clone()));
+    }
+    signed.sort();
+
+    let canonical_headers: String = signed
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value))
+        .collect();
+    let signed_headers = signed
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method,
+        path,
+        canonical_headers,
+        signed_headers,
+        hex::encode(Sha256::digest(body))
+    );
+
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let signing_key = [region, service, "aws4_request"].iter().fold(
+        hmac_sha256(
+            format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+            date.as_bytes(),
+        ),
+        |key, part| hmac_sha256(&key, part.as_bytes()),
+    );
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    signed.retain(|(name, _)| name != "host");
+    signed.push((
+        "authorization".to_string(),
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            credentials.access_key_id, scope, signed_headers, signature
+        ),
+    ));
+    signed
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// Reads `key = value` pairs from one `[section]` of an INI-style AWS file.
+fn read_ini_section(path: &PathBuf, section: &str) -> Option<HashMap<String, String>> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let mut values = HashMap::new();
+    let mut in_section = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_section = name.trim() == section;
+        } else if in_section {
+            if let Some((key, value)) = line.split_once('=') {
+                values.insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+    }
+
+    Some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// The credentials and time used throughout the AWS SigV4 test suite.
+    fn example() -> (AwsCredentials, DateTime<Utc>) {
+        (
+            AwsCredentials {
+                access_key_id: "AKIDEXAMPLE".to_string(),
+                secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+                session_token: None,
+            },
+            Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap(),
+        )
+    }
+
+    fn header<'a>(headers: &'a [(String, String)], name: &str) -> &'a str {
+        headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_else(|| panic!("missing {} header", name))
+    }
+
+    #[test]
+    fn signs_get_vanilla() {
+        let (credentials, now) = example();
+        let headers = sign_request(
+            &credentials,
+            "us-east-1",
+            "service",
+            "GET",
+            "example.amazonaws.com",
+            "/",
+            &[],
+            b"",
+            now,
+        );
+
+        assert_eq!(header(&headers, "x-amz-date"), "20150830T123600Z");
+        assert_eq!(
+            header(&headers, "authorization"),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+        // reqwest sets the host from the URL
+        assert!(headers.iter().all(|(name, _)| name != "host"));
+    }
+
+    #[test]
+    fn signs_lightsail_json_post() {
+        let (credentials, now) = example();
+        let headers = sign_request(
+            &credentials,
+            "us-east-1",
+            "lightsail",
+            "POST",
+            "lightsail.us-east-1.amazonaws.com",
+            "/",
+            &[
+                ("Content-Type", "application/x-amz-json-1.1"),
+                ("X-Amz-Target", "Lightsail_20161128.GetRegions"),
+            ],
+            br#"{"includeAvailabilityZones":true}"#,
+            now,
+        );
+
+        assert_eq!(
+            header(&headers, "x-amz-target"),
+            "Lightsail_20161128.GetRegions"
+        );
+        assert_eq!(
+            header(&headers, "authorization"),
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/lightsail/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date;x-amz-target, \
+             Signature=44391930bcb572b6aabee3b6ca14402645d0cac94c5690917d9fb17976079270"
+        );
+    }
+}
@@ -0,0 +1,378 @@
+//! Local record of the servers deployed from this machine.
+//!
+//! The provider API stays the source of truth; the state file lets `status`
+//! work without credentials or network, and is reconciled whenever the
+//! provider is queried.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{
+    format_age, hours_since, DeployedServer, ProviderKind, ServerList, VpnKind, HOURS_PER_MONTH,
+};
+
+const STATE_VERSION: u32 = 1;
+
+/// A server deployed (or found) by this tool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    pub provider: ProviderKind,
+    pub id: String,
+    pub name: String,
+    /// Region and size slugs; `None` when the provider default was used and
+    /// the server has not been seen in a listing since.
+    pub region: Option<String>,
+    pub size: Option<String>,
+    /// `None` for servers adopted from a provider listing, which does not
+    /// tell the VPNs apart.
+    #[serde(default)]
+    pub vpn: Option<VpnKind>,
+    /// `None` for servers that do not join a tailnet, or whose VPN is unknown.
+    pub tailnet_hostname: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// `CLOUD_INIT_VERSION` the server was set up with; `None` for servers
+    /// that were not deployed from this machine.
+    pub cloud_init_version: Option<u32>,
+    /// Whether `--cloud-init-extra` was merged into the stock config.
+    #[serde(default)]
+    pub cloud_init_extra: bool,
+    pub monthly_cost: Option<f64>,
+    pub currency: Option<String>,
+}
+
+impl DeploymentRecord {
+    /// Record for a server found on the provider but missing locally.
+    pub fn adopted(provider: ProviderKind, server: &DeployedServer) -> Self {
+        Self {
+            provider,
+            id: server.id.clone(),
+            name: server.name.clone(),
+            region: Some(server.region.clone()),
+            size: Some(server.size.clone()),
+            // Tagged servers look the same whatever VPN they run
+            vpn: None,
+            tailnet_hostname: None,
+            created_at: server.created_at,
+            cloud_init_version: None,
+            cloud_init_extra: false,
+            monthly_cost: None,
+            currency: None,
+        }
+    }
+
+    pub fn age(&self) -> String {
+        format_age(self.created_at)
+    }
+
+    /// Cost so far at the price recorded when the server was deployed.
+    pub fn accumulated_cost(&self) -> Option<f64> {
+        self.monthly_cost
+            .map(|monthly| monthly * hours_since(self.created_at) / HOURS_PER_MONTH)
+    }
+
+    /// Cost so far and the monthly rate, e.g. `$1.23 ($4.00/month)`.
+    pub fn format_cost(&self) -> String {
+        match (self.accumulated_cost(), self.monthly_cost, &self.currency) {
+            (Some(accumulated), Some(monthly), Some(currency)) => format!(
+                "{}{:.2} ({}{:.2}/month)",
+                currency, accumulated, currency, monthly
+            ),
+            _ => "unknown".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StateFile {
+    version: u32,
+    deployments: Vec<DeploymentRecord>,
+}
+
+impl Default for StateFile {
+    fn default() -> Self {
+        Self {
+            version: STATE_VERSION,
+            deployments: Vec::new(),
+        }
+    }
+}
+
+/// The state file at `$XDG_DATA_HOME/vpn-deployer/state.json` (or the
+/// platform equivalent).
+pub struct StateStore {
+    path: PathBuf,
+    state: StateFile,
+}
+
+impl StateStore {
+    pub fn open() -> Result<Self> {
+        let path = data_dir()?.join("state.json");
+
+        let state = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => StateFile::default(),
+            Err(e) => return Err(anyhow!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        Ok(Self { path, state })
+    }
+
+    /// Opens the store, applies `update` and saves it.
+    pub fn update(update: impl FnOnce(&mut StateStore)) -> Result<()> {
+        let mut store = Self::open()?;
+        update(&mut store);
+        store.save()
+    }
+
+    pub fn deployments(&self) -> &[DeploymentRecord] {
+        &self.state.deployments
+    }
+
+    /// The VPN the server was deployed with, if it is recorded.
+    pub fn vpn(&self, provider: ProviderKind, id: &str) -> Option<VpnKind> {
+        self.state
+            .deployments
+            .iter()
+            .find(|record| record.provider == provider && record.id == id)
+            .and_then(|record| record.vpn)
+    }
+
+    pub fn find(&self, provider: ProviderKind, target: &str) -> Vec<&DeploymentRecord> {
+        self.state
+            .deployments
+            .iter()
+            .filter(|record| {
+                record.provider == provider && (record.id == target || record.name == target)
+            })
+            .collect()
+    }
+
+    /// Adds `record`, replacing any existing record for the same server.
+    pub fn insert(&mut self, record: DeploymentRecord) {
+        self.remove(record.provider, &record.id);
+        self.state.deployments.push(record);
+    }
+
+    pub fn remove(&mut self, provider: ProviderKind, id: &str) {
+        self.state
+            .deployments
+            .retain(|record| !(record.provider == provider && record.id == id));
+    }
+
+    /// Brings the records for `provider` in line with its current listing:
+    /// servers that are gone are dropped, new ones adopted and region and
+    /// size filled in from what the provider reports. Records in regions
+    /// that could not be listed are kept as they are.
+    pub fn reconcile(&mut self, provider: ProviderKind, list: &ServerList) {
+        let unlisted = |record: &DeploymentRecord| {
+            !list.failed_regions.is_empty()
+                && record
+                    .region
+                    .as_ref()
+                    .is_none_or(|region| list.failed_regions.contains(region))
+        };
+        self.state.deployments.retain(|record| {
+            record.provider != provider
+                || unlisted(record)
+                || list.servers.iter().any(|server| server.id == record.id)
+        });
+
+        for server in &list.servers {
+            let existing = self
+                .state
+                .deployments
+                .iter_mut()
+                .find(|record| record.provider == provider && record.id == server.id);
+            match existing {
+                Some(record) => {
+                    record.region = Some(server.region.clone());
+                    record.size = Some(server.size.clone());
+                }
+                None => self
+                    .state
+                    .deployments
+                    .push(DeploymentRecord::adopted(provider, server)),
+            }
+        }
+
+        self.state
+            .deployments
+            .sort_by_key(|record| record.created_at);
+    }
+
+    /// Writes the state file, replacing it atomically.
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| anyhow!("Failed to create {}: {}", dir.display(), e))?;
+        }
+
+        let contents = serde_json::to_string_pretty(&self.state)?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, contents)
+            .map_err(|e| anyhow!("Failed to write {}: {}", tmp_path.display(), e))?;
+        fs::rename(&tmp_path, &self.path)
+            .map_err(|e| anyhow!("Failed to write {}: {}", self.path.display(), e))?;
+
+        Ok(())
+    }
+}
+
+/// `$XDG_DATA_HOME/vpn-deployer` (or the platform equivalent).
+fn data_dir() -> Result<PathBuf> {
+    Ok(dirs::data_dir()
+        .ok_or_else(|| anyhow!("Could not determine the data directory"))?
+        .join("vpn-deployer"))
+}
+
+/// Where the client configs for `server_name` are written.
+pub fn client_config_dir(server_name: &str) -> Result<PathBuf> {
+    Ok(data_dir()?.join("clients").join(server_name))
+}
+
+/// Writes a file only the current user can read, since client configs hold
+/// private keys.
+pub fn write_private_file(path: &Path, contents: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| anyhow!("Failed to create {}: {}", dir.display(), e))?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))?;
+    std::io::Write::write_all(&mut file, contents.as_bytes())
+        .map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn server(id: &str, region: &str) -> DeployedServer {
+        DeployedServer {
+            id: id.to_string(),
+            name: format!("tailscale-vpn-{}", id),
+            region: region.to_string(),
+            size: "s-1vcpu-512mb-10gb".to_string(),
+            status: "active".to_string(),
+            ip: None,
+            created_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+        }
+    }
+
+    fn record(provider: ProviderKind, id: &str, region: Option<&str>) -> DeploymentRecord {
+        DeploymentRecord {
+            region: region.map(str::to_string),
+            size: None,
+            vpn: Some(VpnKind::WireGuard),
+            tailnet_hostname: None,
+            cloud_init_version: Some(3),
+            monthly_cost: Some(4.0),
+            currency: Some("$".to_string()),
+            ..DeploymentRecord::adopted(provider, &server(id, "ams3"))
+        }
+    }
+
+    fn store(records: Vec<DeploymentRecord>) -> StateStore {
+        StateStore {
+            path: PathBuf::new(),
+            state: StateFile {
+                deployments: records,
+                ..StateFile::default()
+            },
+        }
+    }
+
+    fn ids(store: &StateStore) -> Vec<(ProviderKind, &str)> {
+        store
+            .deployments()
+            .iter()
+            .map(|record| (record.provider, record.id.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn reconcile_adopts_new_servers_with_unknown_vpn() {
+        let mut store = store(vec![]);
+        let list: ServerList = vec![server("1", "ams3")].into_iter().collect();
+
+        store.reconcile(ProviderKind::DigitalOcean, &list);
+
+        let [adopted] = store.deployments() else {
+            panic!("expected one record");
+        };
+        assert_eq!(adopted.id, "1");
+        assert_eq!(adopted.region.as_deref(), Some("ams3"));
+        assert_eq!(adopted.vpn, None);
+        assert_eq!(adopted.tailnet_hostname, None);
+        assert_eq!(store.vpn(ProviderKind::DigitalOcean, "1"), None);
+    }
+
+    #[test]
+    fn reconcile_drops_servers_that_are_gone() {
+        let mut store = store(vec![
+            record(ProviderKind::DigitalOcean, "1", Some("ams3")),
+            record(ProviderKind::DigitalOcean, "2", Some("ams3")),
+        ]);
+        let list: ServerList = vec![server("2", "ams3")].into_iter().collect();
+
+        store.reconcile(ProviderKind::DigitalOcean, &list);
+
+        assert_eq!(ids(&store), [(ProviderKind::DigitalOcean, "2")]);
+    }
+
+    #[test]
+    fn reconcile_keeps_local_details_and_other_providers() {
+        let mut store = store(vec![
+            record(ProviderKind::DigitalOcean, "1", None),
+            record(ProviderKind::Hetzner, "1", Some("fsn1")),
+        ]);
+        let list: ServerList = vec![server("1", "ams3")].into_iter().collect();
+
+        store.reconcile(ProviderKind::DigitalOcean, &list);
+
+        assert_eq!(
+            ids(&store),
+            [
+                (ProviderKind::DigitalOcean, "1"),
+                (ProviderKind::Hetzner, "1")
+            ]
+        );
+        let kept = &store.find(ProviderKind::DigitalOcean, "1")[0];
+        assert_eq!(kept.vpn, Some(VpnKind::WireGuard));
+        assert_eq!(kept.monthly_cost, Some(4.0));
+        // Filled in from the listing
+        assert_eq!(kept.region.as_deref(), Some("ams3"));
+        assert_eq!(kept.size.as_deref(), Some("s-1vcpu-512mb-10gb"));
+    }
+
+    #[test]
+    fn reconcile_keeps_records_in_regions_that_failed_to_list() {
+        let mut store = store(vec![
+            record(ProviderKind::Lightsail, "us-east-1/a", Some("us-east-1")),
+            record(ProviderKind::Lightsail, "eu-west-1/b", Some("eu-west-1")),
+        ]);
+        let list = ServerList {
+            servers: vec![],
+            failed_regions: vec!["eu-west-1".to_string()],
+        };
+
+        store.reconcile(ProviderKind::Lightsail, &list);
+
+        assert_eq!(ids(&store), [(ProviderKind::Lightsail, "eu-west-1/b")]);
+    }
+}
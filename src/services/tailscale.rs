@@ -0,0 +1,328 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use serde_json::json;
+use std::time::Duration;
+
+use crate::models::tailscale::*;
+use crate::models::TailnetNode;
+use crate::services::control::ControlServer;
+
+const TAILSCALE_API_BASE: &str = "https://api.tailscale.com/api/v2";
+
+/// How the tool authenticates against the Tailscale API.
+#[derive(Debug, Clone)]
+pub enum TailscaleCredentials {
+    /// An API access token (`tskey-api-...`).
+    ApiKey(String),
+    /// An OAuth client, exchanged for a short-lived access token on connect.
+    OAuth {
+        client_id: String,
+        client_secret: String,
+    },
+}
+
+impl TailscaleCredentials {
+    /// `TS_API_KEY`, or else `TS_OAUTH_CLIENT_ID` and `TS_OAUTH_CLIENT_SECRET`.
+    pub fn from_environment() -> Option<Self> {
+        let env_var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        if let Some(api_key) = env_var("TS_API_KEY") {
+            return Some(Self::ApiKey(api_key));
+        }
+        match (
+            env_var("TS_OAUTH_CLIENT_ID"),
+            env_var("TS_OAUTH_CLIENT_SECRET"),
+        ) {
+            (Some(client_id), Some(client_secret)) => Some(Self::OAuth {
+                client_id,
+                client_secret,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Only OAuth clients are used to generate auth keys, since their keys
+    /// are always tagged rather than owned by a user.
+    pub fn can_generate_auth_keys(&self) -> bool {
+        matches!(self, Self::OAuth { .. })
+    }
+}
+
+/// Client for the Tailscale control plane API.
+#[derive(Clone)]
+pub struct TailscaleClient {
+    client: reqwest::Client,
+    /// Tailnet the API key belongs to; `-` selects the key's default tailnet.
+    tailnet: String,
+}
+
+impl TailscaleClient {
+    pub fn new(api_key: String) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", api_key)).unwrap(),
+        );
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self {
+            client,
+            tailnet: "-".to_string(),
+        }
+    }
+
+    /// Builds a client for `credentials`, exchanging OAuth client credentials
+    /// for an access token first.
+    pub async fn connect(credentials: &TailscaleCredentials) -> Result<Self> {
+        match credentials {
+            TailscaleCredentials::ApiKey(api_key) => Ok(Self::new(api_key.clone())),
+            TailscaleCredentials::OAuth {
+                client_id,
+                client_secret,
+            } => {
+                let token = Self::fetch_oauth_token(client_id, client_secret).await?;
+                Ok(Self::new(token.access_token))
+            }
+        }
+    }
+
+    async fn fetch_oauth_token(client_id: &str, client_secret: &str) -> Result<OAuthTokenResponse> {
+        let response = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?
+            .post(format!("{}/oauth/token", TAILSCALE_API_BASE))
+            .form(&[("client_id", client_id), ("client_secret", client_secret)])
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!(
+                "Failed to get a Tailscale OAuth token: {}",
+                error_text
+            ))
+        }
+    }
+
+    pub async fn list_devices(&self) -> Result<Vec<Device>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/tailnet/{}/devices?fields=all",
+                TAILSCALE_API_BASE, self.tailnet
+            ))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let devices_response: DevicesResponse = response.json().await?;
+            Ok(devices_response.devices)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list Tailscale devices: {}", error_text))
+        }
+    }
+
+    pub async fn get_device(&self, device_id: &str) -> Result<Device> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/device/{}?fields=all",
+                TAILSCALE_API_BASE, device_id
+            ))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to get Tailscale device: {}", error_text))
+        }
+    }
+
+    /// The most recently seen device registered under `hostname`, if any.
+    pub async fn find_device_by_hostname(&self, hostname: &str) -> Result<Option<Device>> {
+        let devices = self.list_devices().await?;
+        Ok(devices
+            .into_iter()
+            .filter(|device| device.hostname == hostname)
+            .max_by_key(|device| device.last_seen))
+    }
+
+    /// Approves a device on tailnets that require device approval.
+    pub async fn authorize_device(&self, device_id: &str) -> Result<()> {
+        let response = self
+            .client
+            .post(format!(
+                "{}/device/{}/authorized",
+                TAILSCALE_API_BASE, device_id
+            ))
+            .json(&json!({ "authorized": true }))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!(
+                "Failed to authorize Tailscale device: {}",
+                error_text
+            ))
+        }
+    }
+
+    /// Enables `routes` on the device, replacing the previously enabled set.
+    /// Only routes the device advertises can be enabled.
+    pub async fn set_routes(&self, device_id: &str, routes: &[String]) -> Result<DeviceRoutes> {
+        let response = self
+            .client
+            .post(format!(
+                "{}/device/{}/routes",
+                TAILSCALE_API_BASE, device_id
+            ))
+            .json(&json!({ "routes": routes }))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to set device routes: {}", error_text))
+        }
+    }
+
+    pub async fn delete_device(&self, device_id: &str) -> Result<()> {
+        let response = self
+            .client
+            .delete(format!("{}/device/{}", TAILSCALE_API_BASE, device_id))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to delete Tailscale device: {}", error_text))
+        }
+    }
+
+    /// Removes every device registered under `hostname` and returns how many
+    /// were deleted. Re-deploys can leave several nodes with the same hostname.
+    pub async fn delete_devices_by_hostname(&self, hostname: &str) -> Result<usize> {
+        let devices = self.list_devices().await?;
+        let mut deleted = 0;
+        for device in devices.iter().filter(|device| device.hostname == hostname) {
+            self.delete_device(&device.id).await?;
+            deleted += 1;
+        }
+        Ok(deleted)
+    }
+
+    pub async fn create_auth_key(&self, request: &CreateAuthKeyRequest) -> Result<AuthKey> {
+        let response = self
+            .client
+            .post(format!(
+                "{}/tailnet/{}/keys",
+                TAILSCALE_API_BASE, self.tailnet
+            ))
+            .json(request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!(
+                "Failed to create Tailscale auth key: {}",
+                error_text
+            ))
+        }
+    }
+}
+
+fn to_tailnet_node(device: Device) -> TailnetNode {
+    TailnetNode {
+        id: device.id,
+        name: device.name,
+        hostname: device.hostname,
+        online: device.connected_to_control,
+        authorized: device.authorized,
+        advertised_routes: device.advertised_routes,
+        enabled_routes: device.enabled_routes,
+    }
+}
+
+#[async_trait]
+impl ControlServer for TailscaleClient {
+    fn name(&self) -> &'static str {
+        "Tailscale"
+    }
+
+    async fn validate_credentials(&self) -> Result<()> {
+        self.list_devices().await.map(|_| ())
+    }
+
+    async fn find_node(&self, hostname: &str) -> Result<Option<TailnetNode>> {
+        let device = self.find_device_by_hostname(hostname).await?;
+        Ok(device.map(to_tailnet_node))
+    }
+
+    /// Authorizes the device and checks it now reports as authorized.
+    async fn authorize_node(&self, node_id: &str) -> Result<()> {
+        self.authorize_device(node_id).await?;
+        if self.get_device(node_id).await?.authorized {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Tailscale device {} is still not authorized",
+                node_id
+            ))
+        }
+    }
+
+    async fn set_routes(&self, node_id: &str, routes: &[String]) -> Result<Vec<String>> {
+        let routes = TailscaleClient::set_routes(self, node_id, routes).await?;
+        Ok(routes.enabled_routes)
+    }
+
+    async fn create_auth_key(
+        &self,
+        description: &str,
+        tags: &[String],
+        expiry: Duration,
+    ) -> Result<String> {
+        let request = CreateAuthKeyRequest {
+            capabilities: KeyCapabilities {
+                devices: DeviceCapabilities {
+                    create: DeviceCreateCapabilities {
+                        reusable: false,
+                        ephemeral: false,
+                        preauthorized: true,
+                        tags: tags.to_vec(),
+                    },
+                },
+            },
+            expiry_seconds: expiry.as_secs(),
+            description: description.to_string(),
+        };
+        let auth_key = TailscaleClient::create_auth_key(self, &request).await?;
+        Ok(auth_key.key)
+    }
+
+    async fn delete_nodes_by_hostname(&self, hostname: &str) -> Result<usize> {
+        self.delete_devices_by_hostname(hostname).await
+    }
+}
@@ -0,0 +1,285 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::models::{Distro, ExitNodeStatus, InboundPort, TailnetNode, VpnConnection, VpnKind};
+use crate::services::cloud_init::{tailscale_cloud_config, CloudConfig};
+use crate::services::control::{ControlServer, ControlServerSettings};
+
+/// Time given to a setup script whose result can't be checked from outside.
+pub const UNVERIFIED_SETUP_WAIT: Duration = Duration::from_secs(75);
+
+/// Generated auth keys only need to last until the server has joined.
+const GENERATED_AUTH_KEY_EXPIRY: Duration = Duration::from_secs(3600);
+
+/// Routes a device advertises with `tailscale up --advertise-exit-node`.
+const EXIT_NODE_ROUTES: [&str; 2] = ["0.0.0.0/0", "::/0"];
+
+const TAILSCALE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Reports progress within the current deployment step.
+pub type Progress<'a> = &'a (dyn Fn(String) + Send + Sync);
+
+/// How a deployment finds out the VPN on a new server is up.
+#[derive(Debug, Clone, Copy)]
+pub enum Readiness {
+    /// The backend polls an API until the server reports in, giving up after
+    /// `timeout`.
+    Polled { timeout: Duration },
+    /// Nothing can be checked from outside, so the setup script gets a fixed
+    /// amount of time.
+    FixedWait(Duration),
+}
+
+/// The VPN software a server is set up with.
+///
+/// The deployment pipeline only talks to the VPN through this trait, so the
+/// VPN and the cloud provider can be chosen independently.
+#[async_trait]
+pub trait VpnBackend: Send + Sync {
+    fn kind(&self) -> VpnKind;
+
+    /// Cloud-config setting the VPN up on a server named `server_name`.
+    fn cloud_config(&self, server_name: &str, distro: Distro) -> CloudConfig;
+
+    /// Ports clients connect to, opened on providers that firewall servers.
+    fn inbound_ports(&self) -> Vec<InboundPort> {
+        Vec::new()
+    }
+
+    fn readiness(&self) -> Readiness;
+
+    /// Polls until the VPN is up, for backends whose readiness is
+    /// `Readiness::Polled`.
+    async fn poll_until_ready(
+        &mut self,
+        _server_name: &str,
+        _progress: Progress<'_>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// How devices connect to the ready server, reachable at `endpoint`.
+    async fn connection(
+        &self,
+        server_name: &str,
+        endpoint: &str,
+        progress: Progress<'_>,
+    ) -> Result<VpnConnection>;
+}
+
+/// A server joining a tailnet as an exit node.
+pub struct TailscaleBackend {
+    auth_key: String,
+    login_server: Option<String>,
+    control: Option<Arc<dyn ControlServer>>,
+    timeout: Duration,
+    /// The server's node, once it has joined.
+    node: Option<TailnetNode>,
+}
+
+impl TailscaleBackend {
+    /// Connects to the control server and settles on the auth key: the one
+    /// given, or a single-use key generated for `server_name`.
+    pub async fn connect(
+        settings: &ControlServerSettings,
+        auth_key: Option<String>,
+        tags: &[String],
+        timeout: Duration,
+        server_name: &str,
+    ) -> Result<Self> {
+        let control = settings.connect().await?;
+        if let Some(control) = &control {
+            control
+                .validate_credentials()
+                .await
+                .map_err(|e| anyhow!("Invalid {} API credentials: {}", control.name(), e))?;
+        }
+
+        let auth_key = match (auth_key, &control) {
+            (Some(auth_key), _) => auth_key,
+            (None, Some(control)) if settings.can_generate_auth_keys() => {
+                // Single-use, so no reusable key ends up in the server's user data
+                control
+                    .create_auth_key(
+                        &format!("vpn-deployer {}", server_name),
+                        tags,
+                        GENERATED_AUTH_KEY_EXPIRY,
+                    )
+                    .await
+                    .map_err(|e| anyhow!("Failed to generate an auth key: {}", e))?
+            }
+            _ => {
+                return Err(anyhow!(
+                    "No auth key provided and none could be generated with the {} API",
+                    settings.name()
+                ))
+            }
+        };
+
+        Ok(Self {
+            auth_key,
+            login_server: settings.login_server.clone(),
+            control,
+            timeout,
+            node: None,
+        })
+    }
+
+    /// Enables the exit node routes the node advertises, keeping any routes
+    /// that are already enabled.
+    async fn approve_exit_node(control: &dyn ControlServer, node: &TailnetNode) -> Result<()> {
+        let missing: Vec<String> = EXIT_NODE_ROUTES
+            .iter()
+            .filter(|route| node.advertised_routes.iter().any(|r| r == *route))
+            .filter(|route| !node.enabled_routes.iter().any(|r| r == *route))
+            .map(|route| route.to_string())
+            .collect();
+        if missing.is_empty() {
+            // Already approved, e.g. by an autoApprovers rule in the tailnet policy
+            return Ok(());
+        }
+
+        let mut routes = node.enabled_routes.clone();
+        routes.extend(missing);
+        let enabled_routes = control.set_routes(&node.id, &routes).await?;
+
+        if EXIT_NODE_ROUTES
+            .iter()
+            .all(|route| enabled_routes.iter().any(|r| r == route))
+        {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "the exit node routes are still not enabled (enabled: {})",
+                enabled_routes.join(", ")
+            ))
+        }
+    }
+}
+
+#[async_trait]
+impl VpnBackend for TailscaleBackend {
+    fn kind(&self) -> VpnKind {
+        VpnKind::Tailscale
+    }
+
+    /// The server name doubles as its tailnet hostname.
+    fn cloud_config(&self, server_name: &str, _distro: Distro) -> CloudConfig {
+        tailscale_cloud_config(&self.auth_key, server_name, self.login_server.as_deref())
+    }
+
+    /// Without API access there is nothing to poll, so the setup only gets a
+    /// fixed grace period.
+    fn readiness(&self) -> Readiness {
+        match self.control {
+            Some(_) => Readiness::Polled {
+                timeout: self.timeout,
+            },
+            None => Readiness::FixedWait(UNVERIFIED_SETUP_WAIT),
+        }
+    }
+
+    /// Polls the coordination server until the server's node is online and
+    /// advertising the exit node route.
+    async fn poll_until_ready(&mut self, server_name: &str, progress: Progress<'_>) -> Result<()> {
+        let Some(control) = &self.control else {
+            return Ok(());
+        };
+
+        let started = Instant::now();
+        let mut waiting_for = "the server to join your tailnet";
+        let mut last_error = None;
+        loop {
+            let elapsed = started.elapsed().as_secs();
+            match control.find_node(server_name).await {
+                // Keys that are not pre-authorized leave the node waiting for
+                // approval on tailnets that require it
+                Ok(Some(node)) if !node.authorized => {
+                    waiting_for = "the server to be authorized";
+                    progress(format!(
+                        "{} is waiting for approval, authorizing it ({}s)...",
+                        node.hostname, elapsed
+                    ));
+                    if let Err(e) = control.authorize_node(&node.id).await {
+                        last_error = Some(e);
+                    }
+                }
+                Ok(Some(node)) if node.online && node.advertises_exit_node() => {
+                    progress(format!("{} is online in your tailnet", node.name));
+                    self.node = Some(node);
+                    return Ok(());
+                }
+                Ok(Some(node)) if node.online => {
+                    waiting_for = "the server to advertise the exit node route";
+                    progress(format!(
+                        "{} is online, waiting for the exit node route ({}s)...",
+                        node.hostname, elapsed
+                    ));
+                }
+                Ok(Some(node)) => {
+                    waiting_for = "the server to come online";
+                    progress(format!(
+                        "{} registered, waiting for it to come online ({}s)...",
+                        node.hostname, elapsed
+                    ));
+                }
+                Ok(None) => progress(format!(
+                    "Installing Tailscale and joining your tailnet ({}s)...",
+                    elapsed
+                )),
+                // Keep polling through transient API errors until the deadline
+                Err(e) => last_error = Some(e),
+            }
+
+            if started.elapsed() >= self.timeout {
+                let mut message = format!(
+                    "Timed out after {}s waiting for {}",
+                    self.timeout.as_secs(),
+                    waiting_for
+                );
+                match last_error {
+                    Some(e) => {
+                        message.push_str(&format!(" (last {} API error: {})", control.name(), e))
+                    }
+                    None => {
+                        message.push_str(". Check /var/log/cloud-init-output.log on the server")
+                    }
+                }
+                return Err(anyhow!(message));
+            }
+
+            tokio::time::sleep(TAILSCALE_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Approves the exit node. A failed approval is reported rather than
+    /// failing the deployment, since the routes can still be approved in the
+    /// admin console.
+    async fn connection(
+        &self,
+        server_name: &str,
+        _endpoint: &str,
+        progress: Progress<'_>,
+    ) -> Result<VpnConnection> {
+        let exit_node = match (&self.control, &self.node) {
+            (Some(control), Some(node)) => {
+                progress("Approving exit node routes...".to_string());
+                match Self::approve_exit_node(control.as_ref(), node).await {
+                    Ok(()) => ExitNodeStatus::Approved,
+                    Err(e) => ExitNodeStatus::ApprovalFailed {
+                        error: e.to_string(),
+                    },
+                }
+            }
+            _ => ExitNodeStatus::NeedsApproval,
+        };
+
+        Ok(VpnConnection::Tailscale {
+            tailnet_hostname: server_name.to_string(),
+            exit_node,
+            login_server: self.login_server.clone(),
+        })
+    }
+}
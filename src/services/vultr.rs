@@ -0,0 +1,306 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::models::vultr::*;
+use crate::models::{
+    DeployedServer, ImageOption, ProviderKind, RegionOption, ServerInfo, ServerList, ServerRequest,
+    SizeOption, SERVER_TAG,
+};
+use crate::services::provider::CloudProvider;
+
+const VULTR_API_BASE: &str = "https://api.vultr.com/v2";
+
+#[derive(Clone)]
+pub struct VultrClient {
+    client: reqwest::Client,
+}
+
+impl VultrClient {
+    pub fn new(token: String) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+
+        Self { client }
+    }
+
+    pub async fn validate_token(&self) -> Result<()> {
+        let response = self
+            .client
+            .get(format!("{}/account", VULTR_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Invalid API key, or API access is not allowed from this IP address"
+            ))
+        }
+    }
+
+    pub async fn fetch_regions(&self) -> Result<Vec<Region>> {
+        let response = self
+            .client
+            .get(format!("{}/regions?per_page=500", VULTR_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let regions_response: RegionsResponse = response.json().await?;
+            Ok(regions_response.regions)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list regions: {}", error_text))
+        }
+    }
+
+    pub async fn fetch_plans(&self) -> Result<Vec<Plan>> {
+        let response = self
+            .client
+            .get(format!("{}/plans?type=vc2&per_page=500", VULTR_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let plans_response: PlansResponse = response.json().await?;
+            Ok(plans_response.plans)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list plans: {}", error_text))
+        }
+    }
+
+    pub async fn fetch_operating_systems(&self) -> Result<Vec<OperatingSystem>> {
+        let response = self
+            .client
+            .get(format!("{}/os?per_page=500", VULTR_API_BASE))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let os_response: OperatingSystemsResponse = response.json().await?;
+            Ok(os_response.os)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list operating systems: {}", error_text))
+        }
+    }
+
+    pub async fn create_instance(&self, request: &ServerRequest) -> Result<Instance> {
+        let mut instance_request = CreateInstanceRequest {
+            label: request.name.clone(),
+            hostname: request.name.clone(),
+            tags: request.tags.clone(),
+            user_data: STANDARD.encode(&request.user_data),
+            ..CreateInstanceRequest::default()
+        };
+
+        if let Some(region) = &request.region {
+            instance_request.region = region.clone();
+        }
+        if let Some(size) = &request.size {
+            instance_request.plan = size.clone();
+        }
+        if let Some(image) = &request.image {
+            instance_request.os_id = image
+                .parse()
+                .map_err(|_| anyhow!("Invalid operating system id: {}", image))?;
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/instances", VULTR_API_BASE))
+            .json(&instance_request)
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let instance_response: InstanceResponse = response.json().await?;
+            Ok(instance_response.instance)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to create instance: {}", error_text))
+        }
+    }
+
+    pub async fn wait_for_instance_ready(&self, instance_id: &str) -> Result<ServerInfo> {
+        let mut attempts = 0;
+        let max_attempts = 60; // 5 minutes with 5-second intervals
+
+        loop {
+            let response = self
+                .client
+                .get(format!("{}/instances/{}", VULTR_API_BASE, instance_id))
+                .send()
+                .await?;
+
+            if response.status().is_success() {
+                let instance_response: InstanceResponse = response.json().await?;
+                let instance = instance_response.instance;
+
+                // Vultr reports 0.0.0.0 until the IP has been assigned
+                if instance.status == "active"
+                    && instance.power_status == "running"
+                    && instance.main_ip != "0.0.0.0"
+                {
+                    let ipv6 = Some(instance.v6_main_ip).filter(|ip| !ip.is_empty());
+
+                    return Ok(ServerInfo {
+                        name: instance.label,
+                        region: instance.region,
+                        ip: instance.main_ip,
+                        ipv6,
+                    });
+                }
+            }
+
+            attempts += 1;
+            if attempts >= max_attempts {
+                return Err(anyhow!("Timeout waiting for instance to be ready"));
+            }
+
+            sleep(Duration::from_secs(5)).await;
+        }
+    }
+
+    pub async fn fetch_tagged_instances(&self, tag: &str) -> Result<Vec<Instance>> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/instances?tag={}&per_page=500",
+                VULTR_API_BASE, tag
+            ))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let instances_response: InstancesResponse = response.json().await?;
+            Ok(instances_response.instances)
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to list instances: {}", error_text))
+        }
+    }
+
+    pub async fn delete_instance(&self, instance_id: &str) -> Result<()> {
+        let response = self
+            .client
+            .delete(format!("{}/instances/{}", VULTR_API_BASE, instance_id))
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let error_text = response.text().await?;
+            Err(anyhow!("Failed to delete instance: {}", error_text))
+        }
+    }
+}
+
+#[async_trait]
+impl CloudProvider for VultrClient {
+    fn name(&self) -> &'static str {
+        "Vultr"
+    }
+
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Vultr
+    }
+
+    fn default_size(&self) -> String {
+        CreateInstanceRequest::default().plan
+    }
+
+    fn default_image(&self) -> String {
+        CreateInstanceRequest::default().os_id.to_string()
+    }
+
+    async fn validate_credentials(&self) -> Result<()> {
+        self.validate_token().await
+    }
+
+    async fn list_regions(&self) -> Result<Vec<RegionOption>> {
+        let regions = self.fetch_regions().await?;
+        Ok(regions
+            .into_iter()
+            .map(|region| RegionOption {
+                name: region.city,
+                description: format!("{} - {}", region.id.to_uppercase(), region.country),
+                slug: region.id,
+            })
+            .collect())
+    }
+
+    async fn list_sizes(&self) -> Result<Vec<SizeOption>> {
+        let plans = self.fetch_plans().await?;
+        Ok(plans
+            .into_iter()
+            .map(|plan| SizeOption {
+                slug: plan.id,
+                memory: plan.ram,
+                vcpus: plan.vcpu_count,
+                disk: plan.disk,
+                transfer: plan.bandwidth as f64 / 1000.0,
+                price_monthly: plan.monthly_cost,
+                currency: "$",
+                region_prices: HashMap::new(),
+                regions: plan.locations,
+            })
+            .collect())
+    }
+
+    async fn list_images(&self) -> Result<Vec<ImageOption>> {
+        let operating_systems = self.fetch_operating_systems().await?;
+        Ok(operating_systems
+            .into_iter()
+            .filter(|os| os.arch == "x64")
+            .filter_map(|os| ImageOption::supported(os.id.to_string(), &os.family, &os.name))
+            .collect())
+    }
+
+    async fn create_server(&self, request: &ServerRequest) -> Result<String> {
+        let instance = self.create_instance(request).await?;
+        Ok(instance.id)
+    }
+
+    async fn wait_for_server_ready(&self, server_id: &str) -> Result<ServerInfo> {
+        self.wait_for_instance_ready(server_id).await
+    }
+
+    async fn list_servers(&self) -> Result<ServerList> {
+        let instances = self.fetch_tagged_instances(SERVER_TAG).await?;
+        Ok(instances
+            .into_iter()
+            .map(|instance| DeployedServer {
+                id: instance.id,
+                name: instance.label,
+                region: instance.region,
+                size: instance.plan,
+                status: instance.power_status,
+                ip: Some(instance.main_ip).filter(|ip| ip != "0.0.0.0"),
+                created_at: instance.date_created,
+            })
+            .collect())
+    }
+
+    async fn destroy_server(&self, server_id: &str) -> Result<()> {
+        self.delete_instance(server_id).await
+    }
+}
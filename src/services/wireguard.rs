@@ -0,0 +1,209 @@
+//! Plain WireGuard servers, for devices that can't run Tailscale.
+//!
+//! All keys are generated locally: the server's config goes into the user
+//! data and the client configs are written next to the state file.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::models::{ClientProfile, Distro, InboundPort, Protocol, VpnConnection, VpnKind};
+use crate::services::cloud_init::{nat_rules, wireguard_cloud_config, CloudConfig};
+use crate::services::state::{client_config_dir, write_private_file};
+use crate::services::vpn::{Progress, Readiness, VpnBackend, UNVERIFIED_SETUP_WAIT};
+
+pub const WIREGUARD_PORT: InboundPort = InboundPort {
+    port: 51820,
+    protocol: Protocol::Udp,
+};
+
+/// Clients get consecutive addresses after the server's in a /24.
+pub const MAX_WIREGUARD_CLIENTS: usize = 253;
+
+const TUNNEL_PREFIX_V4: &str = "10.66.66";
+const TUNNEL_PREFIX_V6: &str = "fd42:42:42";
+const CLIENT_DNS: &str = "1.1.1.1, 2606:4700:4700::1111";
+
+/// A Curve25519 key pair in the base64 form WireGuard configs use.
+#[derive(Debug, Clone)]
+pub struct KeyPair {
+    pub private_key: String,
+    pub public_key: String,
+}
+
+impl KeyPair {
+    pub fn generate() -> Self {
+        let secret = StaticSecret::random();
+        let public = PublicKey::from(&secret);
+        Self {
+            private_key: STANDARD.encode(secret.to_bytes()),
+            public_key: STANDARD.encode(public.as_bytes()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WireGuardClient {
+    pub name: String,
+    pub keys: KeyPair,
+    /// Host part of the client's tunnel addresses.
+    host: usize,
+}
+
+/// Keys and addresses for a server and its clients.
+#[derive(Debug, Clone)]
+pub struct WireGuardSetup {
+    pub server: KeyPair,
+    pub clients: Vec<WireGuardClient>,
+}
+
+impl WireGuardSetup {
+    pub fn generate(client_count: usize) -> Result<Self> {
+        if client_count == 0 || client_count > MAX_WIREGUARD_CLIENTS {
+            return Err(anyhow!(
+                "WireGuard supports 1 to {} clients",
+                MAX_WIREGUARD_CLIENTS
+            ));
+        }
+
+        let clients = (1..=client_count)
+            .map(|i| WireGuardClient {
+                name: format!("client-{}", i),
+                keys: KeyPair::generate(),
+                host: i + 1,
+            })
+            .collect();
+
+        Ok(Self {
+            server: KeyPair::generate(),
+            clients,
+        })
+    }
+
+    /// `/etc/wireguard/wg0.conf` for the server, masquerading client traffic
+    /// out of the default route's interface.
+    pub fn server_config(&self) -> String {
+        let mut config = format!(
+            "[Interface]\n\
+             Address = {v4}.1/24, {v6}::1/64\n\
+             ListenPort = {port}\n\
+             PrivateKey = {key}\n\
+             PostUp = {post_up}\n\
+             PostDown = {post_down}\n",
+            v4 = TUNNEL_PREFIX_V4,
+            v6 = TUNNEL_PREFIX_V6,
+            port = WIREGUARD_PORT.port,
+            key = self.server.private_key,
+            post_up = firewall_hook("-A"),
+            post_down = firewall_hook("-D"),
+        );
+
+        for client in &self.clients {
+            config.push_str(&format!(
+                "\n[Peer]\n\
+                 # {name}\n\
+                 PublicKey = {key}\n\
+                 AllowedIPs = {v4}.{host}/32, {v6}::{host}/128\n",
+                name = client.name,
+                key = client.keys.public_key,
+                v4 = TUNNEL_PREFIX_V4,
+                v6 = TUNNEL_PREFIX_V6,
+                host = client.host,
+            ));
+        }
+
+        config
+    }
+
+    /// A config sending all of the client's traffic through the server at
+    /// `endpoint`.
+    pub fn client_config(&self, client: &WireGuardClient, endpoint: &str) -> String {
+        format!(
+            "[Interface]\n\
+             PrivateKey = {private_key}\n\
+             Address = {v4}.{host}/32, {v6}::{host}/128\n\
+             DNS = {dns}\n\
+             \n\
+             [Peer]\n\
+             PublicKey = {server_key}\n\
+             Endpoint = {endpoint}:{port}\n\
+             AllowedIPs = 0.0.0.0/0, ::/0\n\
+             PersistentKeepalive = 25\n",
+            private_key = client.keys.private_key,
+            v4 = TUNNEL_PREFIX_V4,
+            v6 = TUNNEL_PREFIX_V6,
+            host = client.host,
+            dns = CLIENT_DNS,
+            server_key = self.server.public_key,
+            endpoint = endpoint,
+            port = WIREGUARD_PORT.port,
+        )
+    }
+
+    /// Writes a `.conf` file per client for the server `server_name`,
+    /// reachable at `endpoint`.
+    pub fn write_client_configs(
+        &self,
+        server_name: &str,
+        endpoint: &str,
+    ) -> Result<Vec<ClientProfile>> {
+        let dir = client_config_dir(server_name)?;
+        self.clients
+            .iter()
+            .map(|client| {
+                let config = self.client_config(client, endpoint);
+                let path = dir.join(format!("{}.conf", client.name));
+                write_private_file(&path, &config)?;
+                Ok(ClientProfile {
+                    name: client.name.clone(),
+                    path,
+                    config,
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl VpnBackend for WireGuardSetup {
+    fn kind(&self) -> VpnKind {
+        VpnKind::WireGuard
+    }
+
+    fn cloud_config(&self, _server_name: &str, distro: Distro) -> CloudConfig {
+        wireguard_cloud_config(&self.server_config(), distro)
+    }
+
+    fn inbound_ports(&self) -> Vec<InboundPort> {
+        vec![WIREGUARD_PORT]
+    }
+
+    fn readiness(&self) -> Readiness {
+        Readiness::FixedWait(UNVERIFIED_SETUP_WAIT)
+    }
+
+    async fn connection(
+        &self,
+        server_name: &str,
+        endpoint: &str,
+        progress: Progress<'_>,
+    ) -> Result<VpnConnection> {
+        progress("Writing client configs...".to_string());
+        let clients = self
+            .write_client_configs(server_name, endpoint)
+            .map_err(|e| anyhow!("Failed to write client configs: {}", e))?;
+        Ok(VpnConnection::WireGuard { clients })
+    }
+}
+
+/// wg-quick hook adding (`-A`) or deleting (`-D`) the forwarding and NAT
+/// rules; wg-quick replaces `%i` with the interface name.
+fn firewall_hook(action: &str) -> String {
+    nat_rules(
+        action,
+        "%i",
+        &format!("{}.0/24", TUNNEL_PREFIX_V4),
+        &format!("{}::/64", TUNNEL_PREFIX_V6),
+    )
+}
@@ -16,20 +16,80 @@ pub fn render(f: &mut Frame, app: &App) {
 
     match &app.state {
         AppState::Welcome => screens::welcome::render(f, chunks[0]),
-        AppState::Auth { token, cursor } => screens::auth::render(f, chunks[0], token, *cursor),
+        AppState::ProviderSelect { selected_index } => {
+            screens::provider_select::render(f, chunks[0], *selected_index)
+        }
+        AppState::Auth { token, cursor } => {
+            screens::auth::render(f, chunks[0], app.provider_kind, token, *cursor)
+        }
+        AppState::SizeSelect { selected_index } => {
+            let sizes = app.available_sizes();
+            screens::size_select::render(f, chunks[0], &sizes, *selected_index)
+        }
         AppState::RegionSelect { selected_index } => {
-            screens::region_select::render(f, chunks[0], *selected_index)
+            let regions = app.available_regions();
+            screens::region_select::render(
+                f,
+                chunks[0],
+                &regions,
+                app.selected_size.as_ref(),
+                *selected_index,
+            )
+        }
+        AppState::ImageSelect { selected_index } => {
+            screens::image_select::render(f, chunks[0], &app.images, *selected_index)
+        }
+        AppState::VpnSelect { selected_index } => {
+            screens::vpn_select::render(f, chunks[0], *selected_index)
         }
         AppState::TailscaleAuth { auth_key, cursor } => {
-            screens::tailscale_auth::render(f, chunks[0], auth_key, *cursor)
+            let generate_tags = app
+                .control
+                .can_generate_auth_keys()
+                .then_some(app.tailscale_tags.as_slice());
+            screens::tailscale_auth::render(f, chunks[0], auth_key, *cursor, generate_tags)
         }
         AppState::Loading { message } => screens::loading::render(f, chunks[0], message),
         AppState::Deploy { progress } => {
+            screens::deploy::render(
+                f,
+                chunks[0],
+                progress,
+                app.selected_region.as_ref(),
+                app.selected_size.as_ref(),
+                app.selected_image.as_ref(),
+            );
+        }
+        AppState::Complete { server_info } => {
             let region_name = app.selected_region.as_ref().map(|r| r.name.as_str());
-            screens::deploy::render(f, chunks[0], progress, region_name);
+            screens::complete::render(f, chunks[0], server_info, app.provider_kind, region_name);
+        }
+        AppState::ClientQr {
+            server_info,
+            client_index,
+        } => screens::client_qr::render(
+            f,
+            chunks[0],
+            server_info.connection.qr_clients(),
+            *client_index,
+        ),
+        AppState::Servers { selected_index } => screens::servers::render(
+            f,
+            chunks[0],
+            &app.servers,
+            app.provider_kind,
+            *selected_index,
+            app.notice.as_deref(),
+        ),
+        AppState::ConfirmDestroy { selected_index } => {
+            if let Some(listing) = app.servers.get(*selected_index) {
+                let has_control_api_access = app.control.has_api_access();
+                screens::confirm_destroy::render(f, chunks[0], listing, has_control_api_access)
+            }
+        }
+        AppState::Error { message } => {
+            screens::error::render(f, chunks[0], message, app.orphaned_server.as_ref())
         }
-        AppState::Complete { server_info } => screens::complete::render(f, chunks[0], server_info),
-        AppState::Error { message } => screens::error::render(f, chunks[0], message),
     }
 }
 
@@ -6,13 +6,14 @@ use ratatui::{
     Frame,
 };
 
+use crate::models::ProviderKind;
 use crate::ui::centered_rect;
 
-pub fn render(f: &mut Frame, area: Rect, token: &str, _cursor: usize) {
+pub fn render(f: &mut Frame, area: Rect, provider: ProviderKind, token: &str, _cursor: usize) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .title("🔐 Step 1: Authentication")
+        .title("🔐 Step 2: Authentication")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Blue));
 
@@ -32,21 +33,85 @@ pub fn render(f: &mut Frame, area: Rect, token: &str, _cursor: usize) {
         .split(inner);
 
     // Instructions
-    let instructions = vec![
-        Line::from(""),
-        Line::from("We need your DigitalOcean API token to create a server."),
-        Line::from(""),
-        Line::from(Span::styled(
-            "📋 Get your token at: https://cloud.digitalocean.com/account/api",
-            Style::default().fg(Color::Cyan),
-        )),
-        Line::from(""),
-        Line::from("Steps:"),
-        Line::from("1. Log in to your DigitalOcean account"),
-        Line::from("2. Go to API → Personal Access Tokens"),
-        Line::from("3. Generate New Token with read/write permissions"),
-        Line::from("4. Copy and paste the token below"),
-    ];
+    let instructions = match provider {
+        ProviderKind::DigitalOcean => vec![
+            Line::from(""),
+            Line::from("We need your DigitalOcean API token to create a server."),
+            Line::from(""),
+            Line::from(Span::styled(
+                "📋 Get your token at: https://cloud.digitalocean.com/account/api",
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(""),
+            Line::from("Steps:"),
+            Line::from("1. Log in to your DigitalOcean account"),
+            Line::from("2. Go to API → Personal Access Tokens"),
+            Line::from("3. Generate New Token with read/write permissions"),
+            Line::from("4. Copy and paste the token below"),
+        ],
+        ProviderKind::Hetzner => vec![
+            Line::from(""),
+            Line::from("We need a Hetzner Cloud API token to create a server."),
+            Line::from(""),
+            Line::from(Span::styled(
+                "📋 Get your token at: https://console.hetzner.cloud",
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(""),
+            Line::from("Steps:"),
+            Line::from("1. Log in and open (or create) a project"),
+            Line::from("2. Go to Security → API Tokens"),
+            Line::from("3. Generate API Token with Read & Write permissions"),
+            Line::from("4. Copy and paste the token below"),
+        ],
+        ProviderKind::Vultr => vec![
+            Line::from(""),
+            Line::from("We need your Vultr API key to create a server."),
+            Line::from(""),
+            Line::from(Span::styled(
+                "📋 Get your key at: https://my.vultr.com/settings/#settingsapi",
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(""),
+            Line::from("Steps:"),
+            Line::from("1. Log in to your Vultr account"),
+            Line::from("2. Go to Account → API and enable API access"),
+            Line::from("3. Allow your current IP address under Access Control"),
+            Line::from("4. Copy and paste the API key below"),
+        ],
+        ProviderKind::Linode => vec![
+            Line::from(""),
+            Line::from("We need a Linode personal access token to create a server."),
+            Line::from(""),
+            Line::from(Span::styled(
+                "📋 Get your token at: https://cloud.linode.com/profile/tokens",
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(""),
+            Line::from("Steps:"),
+            Line::from("1. Log in to Cloud Manager"),
+            Line::from("2. Go to your profile → API Tokens"),
+            Line::from("3. Create a Personal Access Token with Linodes read/write"),
+            Line::from("4. Copy and paste the token below"),
+        ],
+        ProviderKind::Lightsail => vec![
+            Line::from(""),
+            Line::from("We'll use your existing AWS credentials to create a server."),
+            Line::from(""),
+            Line::from(Span::styled(
+                "📋 Credentials are read from AWS_ACCESS_KEY_ID / AWS_SECRET_ACCESS_KEY",
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(Span::styled(
+                "   or from a profile in ~/.aws/credentials and ~/.aws/config",
+                Style::default().fg(Color::Cyan),
+            )),
+            Line::from(""),
+            Line::from("The credentials need the lightsail:* permissions."),
+            Line::from("Type a profile name below, or leave it empty to use"),
+            Line::from("AWS_PROFILE / the default profile."),
+        ],
+    };
 
     let instructions_paragraph = Paragraph::new(instructions)
         .style(Style::default().fg(Color::White))
@@ -55,8 +120,14 @@ pub fn render(f: &mut Frame, area: Rect, token: &str, _cursor: usize) {
     f.render_widget(instructions_paragraph, chunks[0]);
 
     // Token input
-    let token_display = if token.is_empty() {
-        "Enter your DigitalOcean API token...".to_string()
+    let token_display = if !provider.requires_token() {
+        if token.is_empty() {
+            "default".to_string()
+        } else {
+            token.to_string()
+        }
+    } else if token.is_empty() {
+        format!("Enter your {} API token...", provider.display_name())
     } else {
         // Show first 8 chars and mask the rest
         if token.len() > 8 {
@@ -68,7 +139,11 @@ pub fn render(f: &mut Frame, area: Rect, token: &str, _cursor: usize) {
 
     let input_block = Block::default()
         .borders(Borders::ALL)
-        .title("API Token")
+        .title(if provider.requires_token() {
+            "API Token"
+        } else {
+            "AWS Profile"
+        })
         .style(if token.is_empty() {
             Style::default().fg(Color::Gray)
         } else {
@@ -0,0 +1,94 @@
+use qrcode::render::unicode::Dense1x2;
+use qrcode::QrCode;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::models::ClientProfile;
+
+pub fn render(f: &mut Frame, area: Rect, clients: &[ClientProfile], client_index: usize) {
+    let Some(client) = clients.get(client_index) else {
+        return;
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!(
+            "📱 Scan {} ({}/{})",
+            client.name,
+            client_index + 1,
+            clients.len()
+        ))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Green));
+
+    f.render_widget(ratatui::widgets::Clear, area);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(inner);
+
+    // Drawn dark on light whatever the terminal theme, so phones can read it
+    let qr_lines: Vec<String> = match QrCode::new(client.config.as_bytes()) {
+        Ok(code) => code
+            .render::<Dense1x2>()
+            .quiet_zone(true)
+            .build()
+            .lines()
+            .map(|line| line.to_string())
+            .collect(),
+        Err(e) => vec![format!("Could not create a QR code: {}", e)],
+    };
+    let qr_width = qr_lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    if qr_lines.len() > chunks[0].height as usize || qr_width > chunks[0].width as usize {
+        let message = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "Enlarge your terminal to show the QR code",
+                Style::default().fg(Color::Yellow),
+            )),
+        ])
+        .alignment(Alignment::Center);
+        f.render_widget(message, chunks[0]);
+    } else {
+        let qr_area = Rect {
+            x: chunks[0].x + (chunks[0].width - qr_width as u16) / 2,
+            y: chunks[0].y + (chunks[0].height - qr_lines.len() as u16) / 2,
+            width: qr_width as u16,
+            height: qr_lines.len() as u16,
+        };
+        let qr = Paragraph::new(qr_lines.into_iter().map(Line::from).collect::<Vec<_>>())
+            .style(Style::default().fg(Color::Black).bg(Color::White));
+        f.render_widget(qr, qr_area);
+    }
+
+    let footer = vec![
+        Line::from(format!("Saved to {}", client.path.display())),
+        Line::from(Span::styled(
+            "↑/↓ to switch clients, Enter to go back",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+
+    let footer_paragraph = Paragraph::new(footer)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(footer_paragraph, chunks[1]);
+}
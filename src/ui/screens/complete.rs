@@ -7,9 +7,16 @@ use ratatui::{
 };
 
 use crate::app::ServerInfo;
+use crate::models::{ExitNodeStatus, ProviderKind, VpnConnection};
 use crate::ui::centered_rect;
 
-pub fn render(f: &mut Frame, area: Rect, server_info: &ServerInfo) {
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    server_info: &ServerInfo,
+    provider: ProviderKind,
+    region_name: Option<&str>,
+) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
@@ -27,13 +34,13 @@ pub fn render(f: &mut Frame, area: Rect, server_info: &ServerInfo) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(8),
-            Constraint::Length(8),
-            Constraint::Min(0),
+            Constraint::Min(9),
+            Constraint::Length(7),
         ])
         .split(inner);
 
     // Server info
-    let server_lines = vec![
+    let mut server_lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "Your VPN server is now running!",
@@ -51,6 +58,21 @@ pub fn render(f: &mut Frame, area: Rect, server_info: &ServerInfo) {
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
+    ];
+
+    if let Some(ipv6) = &server_info.ipv6 {
+        server_lines.push(Line::from(vec![
+            Span::styled("Server IPv6: ", Style::default().fg(Color::White)),
+            Span::styled(
+                ipv6,
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    server_lines.extend([
         Line::from(vec![
             Span::styled("Server Name: ", Style::default().fg(Color::White)),
             Span::styled(
@@ -69,7 +91,7 @@ pub fn render(f: &mut Frame, area: Rect, server_info: &ServerInfo) {
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
-    ];
+    ]);
 
     let server_paragraph = Paragraph::new(server_lines)
         .style(Style::default().fg(Color::White))
@@ -79,7 +101,7 @@ pub fn render(f: &mut Frame, area: Rect, server_info: &ServerInfo) {
     f.render_widget(server_paragraph, chunks[0]);
 
     // Next steps
-    let next_steps = vec![
+    let mut next_steps = vec![
         Line::from(Span::styled(
             "📱 Next Steps: Connect Your Devices",
             Style::default()
@@ -87,14 +109,112 @@ pub fn render(f: &mut Frame, area: Rect, server_info: &ServerInfo) {
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from("1. Install Tailscale on your devices:"),
-        Line::from("   • Phone/Tablet: Get the app from your app store"),
-        Line::from("   • Computer: Download from tailscale.com/download"),
-        Line::from(""),
-        Line::from("2. Sign in with the same Tailscale account"),
-        Line::from("3. Your devices will automatically connect!"),
     ];
 
+    match &server_info.connection {
+        VpnConnection::Tailscale {
+            tailnet_hostname,
+            exit_node,
+            login_server,
+        } => {
+            next_steps.extend([
+                Line::from("1. Install Tailscale on your devices:"),
+                Line::from("   • Phone/Tablet: Get the app from your app store"),
+                Line::from("   • Computer: Download from tailscale.com/download"),
+                Line::from(""),
+            ]);
+            match login_server {
+                Some(url) => next_steps.extend([
+                    Line::from("2. Join your Headscale server from each device:"),
+                    Line::from(Span::styled(
+                        format!("tailscale up --login-server {}", url),
+                        Style::default().fg(Color::Cyan),
+                    )),
+                ]),
+                None => next_steps.push(Line::from("2. Sign in with the same Tailscale account")),
+            }
+
+            match exit_node {
+                ExitNodeStatus::Approved => next_steps.push(Line::from(format!(
+                    "3. Choose {} as the exit node in the Tailscale app",
+                    tailnet_hostname
+                ))),
+                status => {
+                    match login_server {
+                        Some(_) => next_steps.extend([
+                            Line::from("3. Approve the exit node on the Headscale server:"),
+                            Line::from(Span::styled(
+                                "headscale nodes list",
+                                Style::default().fg(Color::Cyan),
+                            )),
+                            Line::from(Span::styled(
+                                "headscale nodes approve-routes -i <ID> -r 0.0.0.0/0,::/0",
+                                Style::default().fg(Color::Cyan),
+                            )),
+                        ]),
+                        None => next_steps.push(Line::from(
+                            "3. Approve the exit node at login.tailscale.com/admin/machines,",
+                        )),
+                    }
+                    next_steps.push(Line::from("   then choose it in the Tailscale app"));
+                    if let ExitNodeStatus::ApprovalFailed { error } = status {
+                        next_steps.push(Line::from(Span::styled(
+                            format!("⚠️  Automatic approval failed: {}", error),
+                            Style::default().fg(Color::Yellow),
+                        )));
+                    }
+                }
+            }
+        }
+        VpnConnection::WireGuard { clients } => {
+            next_steps.extend([
+                Line::from("1. Install WireGuard on your devices:"),
+                Line::from("   • Phone/Tablet: Get the app from your app store"),
+                Line::from("   • Computer: Download from wireguard.com/install"),
+                Line::from(""),
+                Line::from("2. Press 'r' to scan a client config as a QR code,"),
+            ]);
+            if let Some(dir) = clients.first().and_then(|client| client.path.parent()) {
+                next_steps.push(Line::from(format!(
+                    "   or import one of the {} config(s) in {}",
+                    clients.len(),
+                    dir.display()
+                )));
+            }
+        }
+        VpnConnection::OpenVpn { clients } => {
+            next_steps.extend([
+                Line::from("1. Install OpenVPN Connect on your devices:"),
+                Line::from("   • Phone/Tablet: Get the app from your app store"),
+                Line::from("   • Computer: Download from openvpn.net/client"),
+                Line::from(""),
+            ]);
+            if let Some(dir) = clients.first().and_then(|client| client.path.parent()) {
+                next_steps.push(Line::from(format!(
+                    "2. Import one of the {} .ovpn profile(s) in {}",
+                    clients.len(),
+                    dir.display()
+                )));
+            }
+        }
+        VpnConnection::Shadowsocks { clients } => {
+            next_steps.extend([
+                Line::from("1. Install Outline or a Shadowsocks app on your devices"),
+                Line::from(""),
+                Line::from("2. Add an access URL, or press 'r' to scan one as a QR code:"),
+            ]);
+            for client in clients {
+                next_steps.push(Line::from(Span::styled(
+                    client.config.as_str(),
+                    Style::default().fg(Color::Cyan),
+                )));
+            }
+            if let Some(dir) = clients.first().and_then(|client| client.path.parent()) {
+                next_steps.push(Line::from(format!("   (also saved in {})", dir.display())));
+            }
+        }
+    }
+
     let next_steps_paragraph = Paragraph::new(next_steps)
         .style(Style::default().fg(Color::White))
         .alignment(Alignment::Center)
@@ -106,8 +226,14 @@ pub fn render(f: &mut Frame, area: Rect, server_info: &ServerInfo) {
     let footer_text = vec![
         Line::from(""),
         Line::from("🔒 Your VPN is secure and private"),
-        Line::from("🌍 Access the internet from New York"),
-        Line::from("💡 Manage your server at cloud.digitalocean.com"),
+        Line::from(format!(
+            "🌍 Access the internet from {}",
+            region_name.unwrap_or("your server's region")
+        )),
+        Line::from(format!(
+            "💡 Manage your server at {}",
+            provider.console_url()
+        )),
         Line::from(""),
         Line::from(Span::styled(
             "Press Enter to exit or 'q' to quit",
@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::ServerListing;
+use crate::ui::centered_rect;
+
+pub fn render(f: &mut Frame, area: Rect, listing: &ServerListing, has_control_api_access: bool) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title("🗑️  Destroy Server")
+        .title_alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Red));
+
+    let confirm_area = centered_rect(70, 50, area);
+    f.render_widget(ratatui::widgets::Clear, confirm_area);
+
+    let inner = block.inner(confirm_area);
+    f.render_widget(block, confirm_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(inner);
+
+    let server = &listing.server;
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "This permanently deletes the server:",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            server.name.as_str(),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!(
+            "{} • {} • {}",
+            server.region,
+            server.size,
+            server.ip.as_deref().unwrap_or("no public IP")
+        )),
+        Line::from(format!("Cost so far: {}", listing.format_cost())),
+        Line::from(""),
+    ];
+
+    if has_control_api_access {
+        lines.push(Line::from(
+            "Its device will also be removed from your tailnet.",
+        ));
+    } else {
+        lines.push(Line::from(
+            "💡 Set TS_API_KEY (or HEADSCALE_API_KEY) to also remove its device from your tailnet.",
+        ));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(paragraph, chunks[0]);
+
+    // Footer
+    let footer_text = vec![Line::from(Span::styled(
+        "Press Enter to destroy or 'n' to cancel",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))];
+
+    let footer_paragraph = Paragraph::new(footer_text)
+        .style(Style::default().fg(Color::White))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(footer_paragraph, chunks[1]);
+}
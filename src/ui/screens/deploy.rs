@@ -7,13 +7,27 @@ use ratatui::{
 };
 
 use crate::app::DeployProgress;
+use crate::models::{ImageOption, RegionOption, SizeOption};
 use crate::ui::centered_rect;
 
-pub fn render(f: &mut Frame, area: Rect, progress: &DeployProgress, region_name: Option<&str>) {
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    progress: &DeployProgress,
+    region: Option<&RegionOption>,
+    size: Option<&SizeOption>,
+    image: Option<&ImageOption>,
+) {
+    let cost = match (size, region) {
+        (Some(size), Some(region)) => Some(size.format_price(size.price_in(&region.slug))),
+        (Some(size), None) => Some(size.format_price(size.price_monthly)),
+        _ => None,
+    };
+
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .title("🚀 Step 4: Deploying Your VPN Server")
+        .title("🚀 Step 8: Deploying Your VPN Server")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Blue));
 
@@ -34,26 +48,33 @@ pub fn render(f: &mut Frame, area: Rect, progress: &DeployProgress, region_name:
         .split(inner);
 
     // Step indicators
-    let steps = vec![
-        ("✓", "Validating credentials", progress.current_step > 1),
+    let steps = [
+        (
+            "✓",
+            "Validating credentials".to_string(),
+            progress.current_step > 1,
+        ),
         (
             "⏳",
-            "Creating server ($4/month)",
+            match &cost {
+                Some(cost) => format!("Creating server ({})", cost),
+                None => "Creating server".to_string(),
+            },
             progress.current_step > 2,
         ),
         (
             "⏳",
-            "Waiting for server to be ready",
+            "Waiting for server to be ready".to_string(),
             progress.current_step > 3,
         ),
         (
             "⏳",
-            "Installing and configuring Tailscale",
+            "Installing and configuring Tailscale".to_string(),
             progress.current_step > 4,
         ),
         (
             "⏳",
-            "Connecting to your Tailnet",
+            "Connecting to your Tailnet".to_string(),
             progress.current_step > 5,
         ),
     ];
@@ -63,13 +84,7 @@ pub fn render(f: &mut Frame, area: Rect, progress: &DeployProgress, region_name:
         .enumerate()
         .map(|(i, (_icon, text, completed))| {
             let step_num = i + 1;
-            let icon_char = if *completed {
-                "✓"
-            } else if step_num == progress.current_step {
-                "⏳"
-            } else {
-                "⏳"
-            };
+            let icon_char = if *completed { "✓" } else { "⏳" };
 
             let style = if *completed {
                 Style::default().fg(Color::Green)
@@ -121,14 +136,24 @@ pub fn render(f: &mut Frame, area: Rect, progress: &DeployProgress, region_name:
     f.render_widget(status_paragraph, chunks[2]);
 
     // Info text
-    let region_display = region_name.unwrap_or("New York");
-    let info_text = vec![
+    let region_display = region.map(|r| r.name.as_str()).unwrap_or("Default region");
+    let mut info_text = vec![
         Line::from("This usually takes 2-3 minutes..."),
         Line::from(""),
-        Line::from("💰 Server cost: $4/month (~$0.006/hour)"),
-        Line::from(format!("📍 Server location: {}", region_display)),
-        Line::from("💾 Server specs: 512MB RAM, 1 CPU, 10GB SSD"),
     ];
+    if let Some(cost) = &cost {
+        info_text.push(Line::from(format!("💰 Server cost: {}", cost)));
+    }
+    info_text.push(Line::from(format!(
+        "📍 Server location: {}",
+        region_display
+    )));
+    if let Some(size) = size {
+        info_text.push(Line::from(format!("💾 Server specs: {}", size.specs())));
+    }
+    if let Some(image) = image {
+        info_text.push(Line::from(format!("🐧 Operating system: {}", image.name)));
+    }
 
     let info_paragraph = Paragraph::new(info_text)
         .style(Style::default().fg(Color::Gray))
@@ -6,9 +6,10 @@ use ratatui::{
     Frame,
 };
 
+use crate::app::CreatedServer;
 use crate::ui::centered_rect;
 
-pub fn render(f: &mut Frame, area: Rect, message: &str) {
+pub fn render(f: &mut Frame, area: Rect, message: &str, orphaned_server: Option<&CreatedServer>) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
@@ -28,7 +29,7 @@ pub fn render(f: &mut Frame, area: Rect, message: &str) {
         .split(inner);
 
     // Error message
-    let error_lines = vec![
+    let mut error_lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "Something went wrong:",
@@ -37,14 +38,30 @@ pub fn render(f: &mut Frame, area: Rect, message: &str) {
         Line::from(""),
         Line::from(Span::styled(message, Style::default().fg(Color::White))),
         Line::from(""),
+    ];
+
+    if let Some(server) = orphaned_server {
+        error_lines.push(Line::from(Span::styled(
+            format!(
+                "⚠️  The server {} was created and is still running (and billed).",
+                server.name
+            ),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+        error_lines.push(Line::from(""));
+    }
+
+    error_lines.extend([
         Line::from("Common issues:"),
-        Line::from("• Invalid DigitalOcean API token"),
+        Line::from("• Invalid cloud provider API token"),
         Line::from("• Insufficient permissions on the token"),
         Line::from("• Network connection problems"),
-        Line::from("• DigitalOcean service temporarily unavailable"),
+        Line::from("• Cloud provider service temporarily unavailable"),
         Line::from(""),
         Line::from("💡 Double-check your API token and try again."),
-    ];
+    ]);
 
     let error_paragraph = Paragraph::new(error_lines)
         .style(Style::default().fg(Color::White))
@@ -54,8 +71,13 @@ pub fn render(f: &mut Frame, area: Rect, message: &str) {
     f.render_widget(error_paragraph, chunks[0]);
 
     // Footer
+    let footer_hint = if orphaned_server.is_some() {
+        "Press 'c' to delete the server, Enter to go back or 'q' to quit"
+    } else {
+        "Press Enter to go back or 'q' to quit"
+    };
     let footer_text = vec![Line::from(Span::styled(
-        "Press Enter to go back or 'q' to quit",
+        footer_hint,
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD),
@@ -0,0 +1,93 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::models::ImageOption;
+use crate::ui::centered_rect;
+
+pub fn render(f: &mut Frame, area: Rect, images: &[ImageOption], selected_index: usize) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title("🐧 Step 5: Select Operating System")
+        .title_alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Blue));
+
+    let select_area = centered_rect(70, 60, area);
+    f.render_widget(ratatui::widgets::Clear, select_area);
+
+    let inner = block.inner(select_area);
+    f.render_widget(block, select_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(inner);
+
+    // Instructions
+    let instructions = vec![
+        Line::from("Use ↑/↓ arrows (or W/S/J/K) to select an image"),
+        Line::from("Press Enter to confirm your selection"),
+    ];
+
+    let instructions_paragraph = Paragraph::new(instructions)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(instructions_paragraph, chunks[0]);
+
+    // Image list
+    let items: Vec<ListItem> = images
+        .iter()
+        .enumerate()
+        .map(|(i, image)| {
+            let style = if i == selected_index {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+
+            let prefix = if i == selected_index { "► " } else { "  " };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(image.name.clone(), style),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Available Images")
+            .title_alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Green)),
+    );
+
+    let mut list_state = ListState::default().with_selected(Some(selected_index));
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    // Footer info
+    let footer = vec![
+        Line::from("💡 Ubuntu LTS is selected by default and is the most tested"),
+        Line::from("📦 Packages are installed with apt on Ubuntu/Debian and dnf on Fedora"),
+    ];
+
+    let footer_paragraph = Paragraph::new(footer)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(footer_paragraph, chunks[2]);
+}
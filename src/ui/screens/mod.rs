@@ -1,8 +1,15 @@
 pub mod auth;
+pub mod client_qr;
 pub mod complete;
+pub mod confirm_destroy;
 pub mod deploy;
 pub mod error;
+pub mod image_select;
 pub mod loading;
+pub mod provider_select;
 pub mod region_select;
+pub mod servers;
+pub mod size_select;
 pub mod tailscale_auth;
+pub mod vpn_select;
 pub mod welcome;
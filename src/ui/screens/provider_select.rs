@@ -0,0 +1,101 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::models::ProviderKind;
+use crate::ui::centered_rect;
+
+pub fn render(f: &mut Frame, area: Rect, selected_index: usize) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title("☁️  Step 1: Choose a Cloud Provider")
+        .title_alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Blue));
+
+    let select_area = centered_rect(70, 60, area);
+    f.render_widget(ratatui::widgets::Clear, select_area);
+
+    let inner = block.inner(select_area);
+    f.render_widget(block, select_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(inner);
+
+    // Instructions
+    let instructions = vec![
+        Line::from("Use ↑/↓ arrows (or W/S/J/K) to select a provider"),
+        Line::from("Press Enter to confirm your selection"),
+    ];
+
+    let instructions_paragraph = Paragraph::new(instructions)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(instructions_paragraph, chunks[0]);
+
+    // Provider list
+    let items: Vec<ListItem> = ProviderKind::all()
+        .iter()
+        .enumerate()
+        .map(|(i, provider)| {
+            let style = if i == selected_index {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+
+            let prefix = if i == selected_index { "► " } else { "  " };
+
+            let content = vec![
+                Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(provider.display_name(), style),
+                ]),
+                Line::from(vec![
+                    Span::styled("    ", style),
+                    Span::styled(provider.description(), Style::default().fg(Color::Gray)),
+                ]),
+                Line::from(""),
+            ];
+
+            ListItem::new(content).style(style)
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Supported Providers")
+            .title_alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Green)),
+    );
+
+    f.render_widget(list, chunks[1]);
+
+    // Footer info
+    let footer = vec![
+        Line::from("🔑 You'll need an API token for the provider you choose"),
+        Line::from("💰 You only pay your provider for the server itself"),
+    ];
+
+    let footer_paragraph = Paragraph::new(footer)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(footer_paragraph, chunks[2]);
+}
@@ -2,18 +2,24 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
-use crate::models::RegionOption;
+use crate::models::{RegionOption, SizeOption};
 use crate::ui::centered_rect;
 
-pub fn render(f: &mut Frame, area: Rect, selected_index: usize) {
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    regions: &[RegionOption],
+    size: Option<&SizeOption>,
+    selected_index: usize,
+) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .title("🌍 Step 2: Select Server Location")
+        .title("🌍 Step 4: Select Server Location")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Blue));
 
@@ -46,7 +52,6 @@ pub fn render(f: &mut Frame, area: Rect, selected_index: usize) {
     f.render_widget(instructions_paragraph, chunks[0]);
 
     // Region list
-    let regions = RegionOption::available_regions();
     let items: Vec<ListItem> = regions
         .iter()
         .enumerate()
@@ -61,11 +66,19 @@ pub fn render(f: &mut Frame, area: Rect, selected_index: usize) {
 
             let prefix = if i == selected_index { "► " } else { "  " };
 
+            let mut title = vec![
+                Span::styled(prefix, style),
+                Span::styled(&region.name, style),
+            ];
+            if let Some(size) = size {
+                title.push(Span::styled(
+                    format!("  {}", size.format_price(size.price_in(&region.slug))),
+                    Style::default().fg(Color::Green),
+                ));
+            }
+
             let content = vec![
-                Line::from(vec![
-                    Span::styled(prefix, style),
-                    Span::styled(&region.name, style),
-                ]),
+                Line::from(title),
                 Line::from(vec![
                     Span::styled("    ", style),
                     Span::styled(&region.description, Style::default().fg(Color::Gray)),
@@ -77,19 +90,30 @@ pub fn render(f: &mut Frame, area: Rect, selected_index: usize) {
         })
         .collect();
 
+    let list_title = match size {
+        Some(size) => format!("Available Regions ({})", size.slug),
+        None => "Available Regions".to_string(),
+    };
+
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Available Regions ($4/month)")
+            .title(list_title)
             .title_alignment(Alignment::Center)
             .style(Style::default().fg(Color::Green)),
     );
 
-    f.render_widget(list, chunks[1]);
+    let mut list_state = ListState::default().with_selected(Some(selected_index));
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
 
     // Footer info
+    let pricing_note = match size {
+        Some(_) => "💰 Only regions offering your server size are shown",
+        None => "💰 Check your provider's pricing page for current prices",
+    };
+
     let footer = vec![
-        Line::from("💰 All regions shown offer $4/month pricing"),
+        Line::from(pricing_note),
         Line::from("📍 Choose the region closest to you for best performance"),
         Line::from("🔒 Your VPN server will be deployed in the selected region"),
     ];
@@ -0,0 +1,126 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::ServerListing;
+use crate::models::ProviderKind;
+use crate::ui::centered_rect;
+
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    servers: &[ServerListing],
+    provider_kind: ProviderKind,
+    selected_index: usize,
+    notice: Option<&str>,
+) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title(format!("📋 My Servers on {}", provider_kind.display_name()))
+        .title_alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Blue));
+
+    let list_area = centered_rect(90, 70, area);
+    f.render_widget(ratatui::widgets::Clear, list_area);
+
+    let inner = block.inner(list_area);
+    f.render_widget(block, list_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(inner);
+
+    let header = Paragraph::new(Line::from(Span::styled(
+        format!(
+            "  {:<26}{:<16}{:<10}{:<17}{:<20}{:<10}{}",
+            "NAME", "REGION", "STATUS", "IP", "SIZE", "AGE", "COST"
+        ),
+        Style::default()
+            .fg(Color::Gray)
+            .add_modifier(Modifier::BOLD),
+    )));
+    f.render_widget(header, chunks[0]);
+
+    if servers.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(""),
+            Line::from("No VPN servers found."),
+            Line::from("Servers created by this tool will show up here."),
+        ])
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+        f.render_widget(empty, chunks[1]);
+    } else {
+        let items: Vec<ListItem> = servers
+            .iter()
+            .enumerate()
+            .map(|(i, listing)| {
+                let style = if i == selected_index {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+
+                let prefix = if i == selected_index { "► " } else { "  " };
+                let server = &listing.server;
+                let status_color = match server.status.as_str() {
+                    "active" | "running" => Color::Green,
+                    _ => Color::Yellow,
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(format!("{:<26}", server.name), style),
+                    Span::styled(format!("{:<16}", server.region), style),
+                    Span::styled(
+                        format!("{:<10}", server.status),
+                        Style::default().fg(status_color),
+                    ),
+                    Span::styled(
+                        format!("{:<17}", server.ip.as_deref().unwrap_or("-")),
+                        style,
+                    ),
+                    Span::styled(format!("{:<20}", server.size), style),
+                    Span::styled(format!("{:<10}", server.age()), style),
+                    Span::styled(listing.format_cost(), Style::default().fg(Color::Green)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items).block(Block::default().borders(Borders::TOP));
+        let mut list_state = ListState::default().with_selected(Some(selected_index));
+        f.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+
+    // Footer info
+    let footer = vec![
+        match notice {
+            Some(notice) => Line::from(Span::styled(notice, Style::default().fg(Color::Green))),
+            None => Line::from("Costs are estimated from each server's age and current pricing"),
+        },
+        Line::from(Span::styled(
+            "Press 'd' to destroy the selected server, Enter to go back or 'q' to quit",
+            Style::default().fg(Color::Yellow),
+        )),
+    ];
+
+    let footer_paragraph = Paragraph::new(footer)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(footer_paragraph, chunks[2]);
+}
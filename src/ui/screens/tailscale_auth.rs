@@ -8,11 +8,18 @@ use ratatui::{
 
 use crate::ui::centered_rect;
 
-pub fn render(f: &mut Frame, area: Rect, auth_key: &str, _cursor: usize) {
+/// `generate_tags` is set when the control server API can generate the key instead.
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    auth_key: &str,
+    _cursor: usize,
+    generate_tags: Option<&[String]>,
+) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .title("🔑 Step 3: Tailscale Authentication")
+        .title("🔑 Step 7: Tailscale Authentication")
         .title_alignment(Alignment::Center)
         .style(Style::default().fg(Color::Blue));
 
@@ -44,13 +51,8 @@ pub fn render(f: &mut Frame, area: Rect, auth_key: &str, _cursor: usize) {
         Line::from("Steps:"),
         Line::from("1. Click the link above (or visit it manually)"),
         Line::from("2. Log in to your Tailscale account"),
-        Line::from("3. Click \"Generate auth key...\""),
-        Line::from("4. Enable these options:"),
-        Line::from(Span::styled(
-            "   ✓ Reusable (REQUIRED - one-time keys will fail!)",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        )),
-        Line::from("   ✓ Ephemeral (optional, for temporary servers)"),
+        Line::from("3. Click \"Generate auth key...\" (a single-use key is fine)"),
+        Line::from("4. Enable Pre-approved if your tailnet requires device approval"),
         Line::from("5. Copy the auth key and paste it below"),
     ];
 
@@ -61,7 +63,9 @@ pub fn render(f: &mut Frame, area: Rect, auth_key: &str, _cursor: usize) {
     f.render_widget(instructions_paragraph, chunks[0]);
 
     // Auth key input
-    let key_display = if auth_key.is_empty() {
+    let key_display = if auth_key.is_empty() && generate_tags.is_some() {
+        "Leave empty to generate a single-use key...".to_string()
+    } else if auth_key.is_empty() {
         "Enter your Tailscale auth key...".to_string()
     } else {
         // Show first 12 chars and mask the rest for security
@@ -88,7 +92,7 @@ pub fn render(f: &mut Frame, area: Rect, auth_key: &str, _cursor: usize) {
     f.render_widget(key_paragraph, chunks[1]);
 
     // Help text
-    let help_text = vec![
+    let mut help_text = vec![
         Line::from(""),
         Line::from(Span::styled(
             "Press Enter to continue or 'q' to quit",
@@ -97,17 +101,33 @@ pub fn render(f: &mut Frame, area: Rect, auth_key: &str, _cursor: usize) {
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
+    ];
+
+    match generate_tags {
+        Some(tags) => help_text.push(Line::from(Span::styled(
+            format!(
+                "✨ Leave empty to generate a single-use key tagged {}",
+                tags.join(", ")
+            ),
+            Style::default().fg(Color::Green),
+        ))),
+        None => help_text.push(Line::from(
+            "✨ No key is needed when TS_OAUTH_CLIENT_ID and TS_OAUTH_CLIENT_SECRET are set",
+        )),
+    }
+
+    help_text.extend([
         Line::from("💡 Auth keys are used securely and not stored permanently."),
         Line::from("🔒 Your key will automatically configure the VPN server."),
         Line::from("🌐 The server will be set up as an exit node for secure browsing."),
         Line::from(""),
         Line::from(Span::styled(
-            "⚠️  Common issues: Make sure the key is REUSABLE and not expired!",
+            "⚠️  Common issues: Make sure the key has not expired or been used already",
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )),
-    ];
+    ]);
 
     let help_paragraph = Paragraph::new(help_text)
         .style(Style::default().fg(Color::White))
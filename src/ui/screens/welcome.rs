@@ -31,25 +31,28 @@ pub fn render(f: &mut Frame, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from("This tool will create a VPN server for you on DigitalOcean."),
+        Line::from("This tool will create a VPN server for you in the cloud."),
         Line::from("No technical knowledge required - just follow the steps!"),
         Line::from(""),
         Line::from("What we'll do:"),
-        Line::from("• Create a $4/month server on DigitalOcean"),
+        Line::from("• Create a low-cost server with your cloud provider"),
         Line::from("• Install and configure Tailscale VPN"),
         Line::from("• Connect your devices to the VPN"),
         Line::from(""),
         Line::from("Requirements:"),
-        Line::from("• DigitalOcean account with API token"),
+        Line::from("• DigitalOcean, Hetzner, Vultr, Linode or AWS account"),
         Line::from("• Tailscale account (free)"),
         Line::from(""),
-        Line::from(""),
         Line::from(Span::styled(
             "Press Enter to continue or 'q' to quit",
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )),
+        Line::from(Span::styled(
+            "Press 'm' to view your deployed servers",
+            Style::default().fg(Color::Yellow),
+        )),
     ];
 
     let paragraph = Paragraph::new(content)